@@ -1,6 +1,7 @@
-use common::grid::Grid;
+use common::grid::{Grid, NEIGHBOR_STENCIL};
 use common::runner::Runner;
 use common::search::{dfs, NoSeenSpace, Order};
+use common::utils::OrdinalDirection;
 
 pub fn main(r: &mut Runner, input: &[u8]) {
     let xmas_grid = r.prep("Parse", || XmasGrid::parse(input));
@@ -9,6 +10,8 @@ pub fn main(r: &mut Runner, input: &[u8]) {
     r.part("Part 1", || part_1(&xmas_grid));
     r.set_tail("Parse");
     r.part("Part 1 (DFS)", || part_1_dfs(&xmas_grid));
+    r.set_tail("Parse");
+    r.part("Part 1 (Rays)", || part_1_rays(&xmas_grid));
     r.part("Part 2", || part_2(&xmas_grid));
     r.connect("Part 1", "Part 2");
 }
@@ -39,7 +42,7 @@ fn part_1_dfs(grid: &XmasGrid) -> u32 {
                 continue;
             }
 
-            for dir in 0..8u8 {
+            for dir in OrdinalDirection::ALL {
                 let next_pos = travel((x, y), dir, 1);
                 if let Some(c) = grid.grid.cell(&next_pos) {
                     search.push((*c, next_pos, dir, 1usize));
@@ -70,6 +73,29 @@ fn part_1_dfs(grid: &XmasGrid) -> u32 {
     total
 }
 
+fn part_1_rays(grid: &XmasGrid) -> u32 {
+    let mut count = 0;
+    for (pos, c) in grid.grid.iter() {
+        if *c != b'X' {
+            continue;
+        }
+
+        for step in NEIGHBOR_STENCIL {
+            if grid
+                .grid
+                .ray(pos, step)
+                .take(4)
+                .map(|(_, c)| *c)
+                .eq(*b"XMAS")
+            {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
 fn part_2(grid: &XmasGrid) -> u32 {
     let mut count = 0;
     let (width, height) = grid.size();
@@ -99,7 +125,7 @@ impl XmasGrid {
 
         if Some(&b'X') == self.grid.cell(&pos) {
             let mut count = 0;
-            for dir in 0..8 {
+            for dir in OrdinalDirection::ALL {
                 for n in 1..4 {
                     if Some(&WORD[n]) == self.grid.cell(&travel(pos, dir, n)) {
                         if n == 3 {
@@ -147,19 +173,12 @@ impl XmasGrid {
     }
 }
 
-fn travel(pos: (usize, usize), dir: u8, n: usize) -> (usize, usize) {
-    let (x, y) = pos;
-    match dir {
-        0 => (x.wrapping_sub(n), y.wrapping_sub(n)),
-        1 => (x, y.wrapping_sub(n)),
-        2 => (x.wrapping_add(n), y.wrapping_sub(n)),
-        3 => (x.wrapping_sub(n), y),
-        4 => (x.wrapping_add(n), y),
-        5 => (x.wrapping_sub(n), y.wrapping_add(n)),
-        6 => (x, y.wrapping_add(n)),
-        7 => (x.wrapping_add(n), y.wrapping_add(n)),
-        _ => unreachable!(),
-    }
+fn travel(pos: (usize, usize), dir: OrdinalDirection, n: usize) -> (usize, usize) {
+    let (dx, dy) = dir.delta::<isize>();
+    (
+        pos.0.wrapping_add_signed(dx * n as isize),
+        pos.1.wrapping_add_signed(dy * n as isize),
+    )
 }
 
 #[cfg(test)]
@@ -231,6 +250,14 @@ SAMX.XMAS
         assert_eq!(part_1(&XmasGrid::parse(EXAMPLE_EDGES_INVERSE)), 12);
     }
 
+    #[test]
+    fn part1_rays_works_on_example() {
+        assert_eq!(part_1_rays(&XmasGrid::parse(EXAMPLE)), 18);
+        assert_eq!(part_1_rays(&XmasGrid::parse(EXAMPLE_STAR)), 8);
+        assert_eq!(part_1_rays(&XmasGrid::parse(EXAMPLE_EDGES)), 12);
+        assert_eq!(part_1_rays(&XmasGrid::parse(EXAMPLE_EDGES_INVERSE)), 12);
+    }
+
     #[test]
     fn part2_works_on_example() {
         assert_eq!(part_2(&XmasGrid::parse(EXAMPLE)), 9);
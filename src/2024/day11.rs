@@ -1,6 +1,7 @@
 use common::parser;
 use common::parser::Parser;
-use common::runner::Runner;
+use common::runner::{Runner, WithExtra};
+use common::utils::{Memo, MemoStats};
 use rustc_hash::{FxBuildHasher, FxHashMap};
 use std::mem;
 
@@ -14,6 +15,9 @@ pub fn main(r: &mut Runner, input: &[u8]) {
     r.set_tail("Parse");
     r.part("Part 1 (Counters)", || part_counters(&pebbles, 25));
     r.part("Part 2 (Counters)", || part_counters(&pebbles, 75));
+    r.set_tail("Parse");
+    let WithExtra(_, stats) = r.part("Part 2 (Memo)", || part_memo(&pebbles, 75));
+    r.info("Memo Stats", &stats);
 
     r.connect("Part 1 (Brute)", "Part 2 (DP)");
     r.connect("Part 1 (Brute)", "Part 2 (Counters)");
@@ -43,6 +47,25 @@ fn part_dp(pebbles: &[u64], times: u32) -> u64 {
     total
 }
 
+fn part_memo(pebbles: &[u64], times: u32) -> WithExtra<u64, MemoStats> {
+    let count = |memo: &mut Memo<(u64, u32), u64>, (pebble, remaining): (u64, u32)| {
+        if remaining == 0 {
+            return 1;
+        }
+
+        match PebbleSplit::calculate(pebble) {
+            PebbleSplit::Split(left, right) => {
+                memo.get((left, remaining - 1)) + memo.get((right, remaining - 1))
+            }
+            PebbleSplit::Replace(pebble) => memo.get((pebble, remaining - 1)),
+        }
+    };
+
+    let mut memo = Memo::new(&count);
+    let total = pebbles.iter().map(|p| memo.get((*p, times))).sum();
+    WithExtra(total, memo.stats())
+}
+
 fn part_counters(pebbles: &[u64], times: u32) -> u64 {
     let mut counts = FxHashMap::with_capacity_and_hasher(2048, FxBuildHasher::default());
     let mut counts2 = FxHashMap::with_capacity_and_hasher(2048, FxBuildHasher::default());
@@ -239,6 +262,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn memo_matches_dp() {
+        let pebbles = parser().parse_value(EXAMPLE).unwrap();
+        assert_eq!(part_memo(&pebbles, 25).0, 55312);
+        assert_eq!(part_memo(&pebbles, 75).0, part_dp(&pebbles, 75));
+    }
+
     #[test]
     fn dp_tests() {
         assert_eq!(part_dp(&[0], 1), 1);
@@ -10,8 +10,12 @@ type UniqueFarmGrid = Grid<(u8, u8), Vec<u16>, u16>;
 
 pub fn main(r: &mut Runner, input: &[u8]) {
     let grid = r.prep("Parse", || parse(input));
-    let WithExtra(_, grid) = r.part("Part 1", || part_1(&grid));
-    r.part("Part 2", || part_2(&grid));
+    let WithExtra(_, unique_grid) = r.part("Part 1", || part_1(&grid));
+    r.part("Part 2", || part_2(&unique_grid));
+    r.set_tail("Parse");
+    r.part("Part 1 (Regions)", || price_regions(&grid, false));
+    r.set_tail("Parse");
+    r.part("Part 2 (Regions)", || price_regions(&grid, true));
 }
 
 fn part_1(farm: &FarmGrid) -> WithExtra<u32, UniqueFarmGrid> {
@@ -138,6 +142,19 @@ fn part_2(farm: &UniqueFarmGrid) -> u32 {
     price
 }
 
+fn price_regions(farm: &FarmGrid, bulk: bool) -> usize {
+    let regions = farm.regions();
+    let border = regions.labels[(0, 0)];
+
+    regions
+        .regions
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != border)
+        .map(|(_, r)| r.area * if bulk { r.sides } else { r.perimeter })
+        .sum()
+}
+
 fn parse(input: &[u8]) -> FarmGrid {
     let width = input.iter().position(|&c| c == b'\n').unwrap();
     let height = input.len() / (width + 1);
@@ -193,4 +210,12 @@ AAAAAA
         assert_eq!(part_2(&part_1(&parse(EXAMPLE_2A)).1), 236);
         assert_eq!(part_2(&part_1(&parse(EXAMPLE_2B)).1), 368);
     }
+
+    #[test]
+    fn regions_work_on_examples() {
+        assert_eq!(price_regions(&parse(EXAMPLE_1A), false), 140);
+        assert_eq!(price_regions(&parse(EXAMPLE_1A), true), 80);
+        assert_eq!(price_regions(&parse(EXAMPLE_2A), true), 236);
+        assert_eq!(price_regions(&parse(EXAMPLE_2B), true), 368);
+    }
 }
@@ -1,6 +1,7 @@
 use common::parser;
 use common::parser::Parser;
 use common::runner::Runner;
+use common::utils::solve_non_negative_integer;
 
 pub fn main(r: &mut Runner, input: &[u8]) {
     let machines = r.prep("Parse", || Machine::parse_list(input));
@@ -8,6 +9,8 @@ pub fn main(r: &mut Runner, input: &[u8]) {
     r.set_tail("Parse");
     r.part("Part 1", || part_1(&machines));
     r.part("Part 2", || part_2(&machines));
+    r.set_tail("Parse");
+    r.part("Part 2 (Solver)", || part_2_solver(&machines));
 
     r.info("Machines", &machines.len());
 }
@@ -31,6 +34,10 @@ fn part_2(machines: &[Machine]) -> i64 {
     part(machines, |m| m.with_correction().solve())
 }
 
+fn part_2_solver(machines: &[Machine]) -> i64 {
+    part(machines, |m| m.with_correction().solve_linear())
+}
+
 struct Machine {
     a: (i64, i64),
     b: (i64, i64),
@@ -98,6 +105,16 @@ impl Machine {
         }
     }
 
+    fn solve_linear(&self) -> Option<i64> {
+        let (ax, ay) = self.a;
+        let (bx, by) = self.b;
+        let (px, py) = self.prize;
+
+        let a = [vec![ax as i128, bx as i128], vec![ay as i128, by as i128]];
+        let presses = solve_non_negative_integer(&a, &[px as i128, py as i128])?;
+        Some((presses[0] * 3 + presses[1]) as i64)
+    }
+
     fn parse_list(input: &[u8]) -> Vec<Self> {
         Self::parser().repeat().parse_value(input).unwrap()
     }
@@ -154,4 +171,11 @@ Prize: X=18641, Y=10279
     fn part1_works_on_example() {
         assert_eq!(part_1(&Machine::parse_list(EXAMPLE)), 480);
     }
+
+    #[test]
+    fn solver_matches_cramer() {
+        let machines = Machine::parse_list(EXAMPLE);
+        assert_eq!(part(&machines, |m| m.solve_linear()), 480);
+        assert_eq!(part_2_solver(&machines), part_2(&machines));
+    }
 }
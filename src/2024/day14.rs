@@ -1,6 +1,7 @@
 use common::parser;
 use common::parser::Parser;
 use common::runner::Runner;
+use common::sim::Particles;
 use common::utils::crt;
 use num::integer::lcm;
 
@@ -9,6 +10,12 @@ pub fn main(r: &mut Runner, input: &[u8]) {
 
     r.part("Part 1", || part_1::<101, 103>(&robots));
     r.part("Part 2", || part_2::<101, 103>(&robots));
+    r.set_tail("Parse");
+    let particles = r.prep("Parse (Particles)", || to_particles(&robots));
+    r.part("Part 1 (Particles)", || part_1_particles(&particles));
+    r.part("Part 2 (Variance)", || {
+        particles.variance_structure().unwrap()
+    });
 
     r.info("Robots", &robots.len());
 }
@@ -80,6 +87,22 @@ fn part_2<const W: u32, const H: u32>(robots: &[Robot<W, H>]) -> u32 {
     0
 }
 
+fn part_1_particles(particles: &Particles) -> usize {
+    particles.frame(100).quadrants().iter().product()
+}
+
+fn to_particles<const W: u32, const H: u32>(robots: &[Robot<W, H>]) -> Particles {
+    let mut particles = Particles::new(W as i64, H as i64);
+    for robot in robots.iter() {
+        particles.push(
+            (robot.p.0 as i64, robot.p.1 as i64),
+            (robot.v.0 as i64, robot.v.1 as i64),
+        );
+    }
+
+    particles
+}
+
 fn quadrants<const W: u32, const H: u32>(robots: &[Robot<W, H>]) -> [u32; 4] {
     let mut quadrants = [0u32; 4];
     for robot in robots.iter() {
@@ -203,4 +226,10 @@ p=9,5 v=-3,-3
     fn part_1_works_on_example() {
         assert_eq!(part_1(&Robot::<11, 7>::parse_list(EXAMPLE)), 12);
     }
+
+    #[test]
+    fn part_1_particles_works_on_example() {
+        let particles = to_particles(&Robot::<11, 7>::parse_list(EXAMPLE));
+        assert_eq!(part_1_particles(&particles), 12);
+    }
 }
@@ -1,6 +1,7 @@
 use arrayvec::ArrayVec;
-use common::grid::Grid;
+use common::grid::{Grid, PushGrid};
 use common::runner::Runner;
+use common::utils::CardinalDirection;
 use std::fmt::{Debug, Formatter};
 
 pub fn main(r: &mut Runner, input: &[u8]) {
@@ -8,6 +9,9 @@ pub fn main(r: &mut Runner, input: &[u8]) {
 
     r.part("Part 1", || part_1(&warehouse, &moves));
     r.part("Part 2", || part_2(&warehouse, &moves));
+    r.set_tail("Parse");
+    r.part("Part 1 (Push)", || part_push(&warehouse, &moves, 1));
+    r.part("Part 2 (Push)", || part_push(&warehouse, &moves, 2));
 
     r.info_debug("Robot Position", &warehouse.robot_pos);
     r.info_debug("Warehouse Size", &warehouse.grid.size());
@@ -28,6 +32,17 @@ fn part_2(warehouse: &Warehouse, moves: &[Move]) -> u32 {
     warehouse.total_gps()
 }
 
+fn part_push(warehouse: &Warehouse, moves: &[Move], width: usize) -> usize {
+    let mut grid = warehouse.to_push_grid(width);
+    let moves: Vec<CardinalDirection> = moves.iter().map(|m| m.direction()).collect();
+    grid.replay(0, &moves);
+
+    grid.objects()[1..]
+        .iter()
+        .map(|o| o.pos.1 * 100 + o.pos.0)
+        .sum()
+}
+
 #[derive(Clone)]
 struct Warehouse {
     grid: Grid<(u8, u8), Vec<Cell>, Cell>,
@@ -90,6 +105,30 @@ impl Warehouse {
         total
     }
 
+    /// The robot is object 0, and the boxes are `width` cells wide.
+    fn to_push_grid(&self, width: usize) -> PushGrid {
+        let (w, h) = *self.grid.size();
+        let mut grid = PushGrid::new((w as usize * width, h as usize));
+        let (rx, ry) = self.robot_pos;
+        grid.add_object((rx as usize * width, ry as usize), (1, 1));
+        for ((x, y), cell) in self.grid.iter() {
+            let pos = (x as usize * width, y as usize);
+            match cell {
+                Cell::Wall => {
+                    for i in 0..width {
+                        grid.add_wall((pos.0 + i, pos.1));
+                    }
+                }
+                Cell::Box => {
+                    grid.add_object(pos, (width, 1));
+                }
+                Cell::Empty => {}
+            }
+        }
+
+        grid
+    }
+
     fn to_wide(&self) -> WideWarehouse {
         WideWarehouse::from_warehouse(self)
     }
@@ -151,42 +190,17 @@ impl Warehouse {
 
 impl Debug for Warehouse {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "   ")?;
-        for n in 0..self.grid.size().0 {
-            if n % 10 == 0 {
-                write!(f, "{}", (n / 10) % 10)?;
-            } else {
-                write!(f, " ")?;
-            }
-        }
-        writeln!(f)?;
-        write!(f, "   ")?;
-        for n in 0..self.grid.size().0 {
-            write!(f, "{}", n % 10)?;
-        }
-        writeln!(f)?;
-
-        for ((x, y), cell) in self.grid.iter() {
-            if x == 0 && y > 0 {
-                writeln!(f)?;
-            }
-
-            if x == 0 {
-                write!(f, "{y:02} ")?;
-            }
-
-            if (x, y) == self.robot_pos {
-                write!(f, "@")?;
-            } else {
-                match cell {
-                    Cell::Empty => write!(f, ".")?,
-                    Cell::Wall => write!(f, "#")?,
-                    Cell::Box => write!(f, "O")?,
-                }
-            }
-        }
+        let render = self
+            .grid
+            .render(|cell| match cell {
+                Cell::Empty => '.',
+                Cell::Wall => '#',
+                Cell::Box => 'O',
+            })
+            .overlay([self.robot_pos], '@')
+            .with_rulers();
 
-        writeln!(f)
+        write!(f, "{render}")
     }
 }
 
@@ -349,26 +363,11 @@ impl WideWarehouse {
     }
 
     fn from_warehouse(warehouse: &Warehouse) -> Self {
-        let (w, h) = warehouse.grid.size();
-        let mut grid = Grid::new_vec((w * 2, *h));
-        for ((x, y), cell) in warehouse.grid.iter() {
-            let x = x * 2;
-
-            match cell {
-                Cell::Empty => {
-                    grid[(x, y)] = WideCell::Empty;
-                    grid[(x + 1, y)] = WideCell::Empty;
-                }
-                Cell::Wall => {
-                    grid[(x, y)] = WideCell::Wall;
-                    grid[(x + 1, y)] = WideCell::Wall;
-                }
-                Cell::Box => {
-                    grid[(x, y)] = WideCell::BoxLeft;
-                    grid[(x + 1, y)] = WideCell::BoxRight;
-                }
-            }
-        }
+        let grid = warehouse.grid.widened(|cell| match cell {
+            Cell::Empty => [WideCell::Empty, WideCell::Empty],
+            Cell::Wall => [WideCell::Wall, WideCell::Wall],
+            Cell::Box => [WideCell::BoxLeft, WideCell::BoxRight],
+        });
 
         Self {
             grid,
@@ -379,43 +378,18 @@ impl WideWarehouse {
 
 impl Debug for WideWarehouse {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "   ")?;
-        for n in 0..self.grid.size().0 {
-            if n % 10 == 0 {
-                write!(f, "{}", (n / 10) % 10)?;
-            } else {
-                write!(f, " ")?;
-            }
-        }
-        writeln!(f)?;
-        write!(f, "   ")?;
-        for n in 0..self.grid.size().0 {
-            write!(f, "{}", n % 10)?;
-        }
-        writeln!(f)?;
-
-        for ((x, y), cell) in self.grid.iter() {
-            if x == 0 && y > 0 {
-                writeln!(f)?;
-            }
-
-            if x == 0 {
-                write!(f, "{y:02} ")?;
-            }
-
-            if (x, y) == self.robot_pos {
-                write!(f, "@")?;
-            } else {
-                match cell {
-                    WideCell::Empty => write!(f, ".")?,
-                    WideCell::Wall => write!(f, "#")?,
-                    WideCell::BoxLeft => write!(f, "[")?,
-                    WideCell::BoxRight => write!(f, "]")?,
-                }
-            }
-        }
+        let render = self
+            .grid
+            .render(|cell| match cell {
+                WideCell::Empty => '.',
+                WideCell::Wall => '#',
+                WideCell::BoxLeft => '[',
+                WideCell::BoxRight => ']',
+            })
+            .overlay([self.robot_pos], '@')
+            .with_rulers();
 
-        writeln!(f)
+        write!(f, "{render}")
     }
 }
 
@@ -445,6 +419,15 @@ enum Move {
 }
 
 impl Move {
+    fn direction(&self) -> CardinalDirection {
+        match self {
+            Move::Up => CardinalDirection::North,
+            Move::Left => CardinalDirection::West,
+            Move::Right => CardinalDirection::East,
+            Move::Down => CardinalDirection::South,
+        }
+    }
+
     #[inline]
     fn side_of_box(&self, box_pos: &[(u8, u8); 2]) -> BoxSide {
         match self {
@@ -576,4 +559,15 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
         let (warehouse, moves) = Warehouse::parse(EXAMPLE_DIAGONALS);
         assert_eq!(part_2(&warehouse, &moves), 1648)
     }
+
+    #[test]
+    fn push_grid_works_on_examples() {
+        let (warehouse, moves) = Warehouse::parse(EXAMPLE_SMALL);
+        assert_eq!(part_push(&warehouse, &moves, 1), 2028);
+        let (warehouse, moves) = Warehouse::parse(EXAMPLE_LARGE);
+        assert_eq!(part_push(&warehouse, &moves, 1), 10092);
+        assert_eq!(part_push(&warehouse, &moves, 2), 9021);
+        let (warehouse, moves) = Warehouse::parse(EXAMPLE_DIAGONALS);
+        assert_eq!(part_push(&warehouse, &moves, 2), 1648);
+    }
 }
@@ -1,6 +1,7 @@
 use common::parser;
 use common::parser::Parser;
 use common::runner::Runner;
+use common::vm::{disassemble, reverse_search, Machine, ThreeBit};
 use num::range_step;
 use std::fmt::Write;
 
@@ -18,8 +19,12 @@ pub fn main(r: &mut Runner, input: &[u8]) {
 
     r.part("Part 1", || part_1(&program, registers));
     r.part("Part 2", || part_2(&program, registers));
+    r.set_tail("Parse");
+    r.part("Part 1 (VM)", || part_1_vm(&program, registers));
+    r.part("Part 2 (VM)", || part_2_vm(&program, registers));
 
     r.info("Program", &print_program(&program));
+    r.info("Program (VM)", &disassemble::<ThreeBit>(&program));
 }
 
 fn part_1(program: &[u8], registers: [u64; 3]) -> String {
@@ -51,6 +56,22 @@ fn part_2(program: &[u8], registers: [u64; 3]) -> u64 {
     .unwrap()
 }
 
+fn part_1_vm(program: &[u8], registers: [u64; 3]) -> String {
+    let mut machine = Machine::<ThreeBit>::new(program, registers);
+    machine.run();
+
+    let digits: Vec<String> = machine.output.iter().map(|d| d.to_string()).collect();
+    digits.join(",")
+}
+
+fn part_2_vm(program: &[u8], registers: [u64; 3]) -> u64 {
+    let mut machine = Machine::<ThreeBit>::new(program, registers);
+    reverse_search(&mut machine, program, 8, |a| {
+        [a, registers[1], registers[2]]
+    })
+    .unwrap()
+}
+
 fn part_2_step(
     a: u64,
     step_size: u64,
@@ -255,6 +276,15 @@ Program: 0,3,5,4,3,0
         assert_eq!(part_1(&program, registers), "4,6,3,5,6,3,5,2,1,0");
     }
 
+    #[test]
+    fn vm_matches_original() {
+        let (program, registers) = parser().parse_value(EXAMPLE_1).unwrap();
+        assert_eq!(part_1_vm(&program, registers), part_1(&program, registers));
+
+        let (program, registers) = parser().parse_value(EXAMPLE_2).unwrap();
+        assert_eq!(part_2_vm(&program, registers), 117440);
+    }
+
     #[test]
     fn part_2_works_on_example() {
         let (program, registers) = parser().parse_value(EXAMPLE_2).unwrap();
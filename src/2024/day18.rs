@@ -3,8 +3,8 @@ use bit_vec::BitVec;
 use common::grid::{Grid, GridCoordinate};
 use common::parser;
 use common::parser::Parser;
-use common::runner::Runner;
-use common::search::{bfs, Key, Order, Search, SeenSpace};
+use common::runner::{Runner, WithExtra};
+use common::search::{bfs, Key, NoStats, Order, Search, SearchStats, SeenSpace, StatsCollector};
 use common::utils::UnionFind;
 
 const START: (u8, u8) = (0, 0);
@@ -23,6 +23,10 @@ pub fn main(r: &mut Runner, input: &[u8]) {
         part_2_union_find(*grid.size(), &points)
     });
 
+    r.set_tail("Parse");
+    let WithExtra(_, stats) = r.part("Part 1 (Stats)", || part_1_stats(&grid));
+    r.info("Search Stats", &stats);
+
     r.info("Bytes", &points.len());
 }
 
@@ -31,11 +35,23 @@ fn part_1(grid: &ByteGrid) -> u32 {
     run_pathfinding(grid, 1024, &mut search).unwrap()
 }
 
-type GridSearch<O> = Search<((u8, u8), u32), SeenGrid, O>;
+fn part_1_stats(grid: &ByteGrid) -> WithExtra<u32, SearchStats> {
+    let mut search = bfs().with_seen_space(SeenGrid::new()).with_stats();
+    let steps = run_pathfinding(grid, 1024, &mut search).unwrap();
+
+    WithExtra(steps, search.stats())
+}
 
-fn run_pathfinding<O>(grid: &ByteGrid, limit: u16, search: &mut GridSearch<O>) -> Option<u32>
+type GridSearch<O, ST = NoStats> = Search<((u8, u8), u32), SeenGrid, O, ST>;
+
+fn run_pathfinding<O, ST>(
+    grid: &ByteGrid,
+    limit: u16,
+    search: &mut GridSearch<O, ST>,
+) -> Option<u32>
 where
     O: Order<((u8, u8), u32)>,
+    ST: StatsCollector,
 {
     search.push((START, 0));
     search.find(|search, ((x, y), cost)| {
@@ -154,7 +170,7 @@ fn part_2_union_find(size: (u8, u8), order: &[(u8, u8)]) -> String {
 
         seen.set(index, true);
 
-        if uf.find(top_right) == uf.find(bottom_left) {
+        if uf.same(top_right, bottom_left) {
             return format!("{x},{y}");
         }
     }
@@ -222,12 +238,26 @@ where
             false
         }
     }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.data.iter().map(|w| w.count_ones() as usize).sum())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn part_1_stats_counts_open_grid() {
+        let grid = ByteGrid::new_with_default((71, 71), [0; 71 * 71], 0);
+        let WithExtra(steps, stats) = part_1_stats(&grid);
+        assert_eq!(steps, 140);
+        assert_eq!(stats.seen_occupancy, Some(stats.pushed));
+        assert!(stats.popped <= stats.pushed);
+        assert!(stats.rejected > 0);
+    }
+
     #[test]
     fn part_2_uf_works_on_example() {
         assert_eq!(part_2_union_find((7, 7), EXAMPLE_POINTS), "6,1",)
@@ -16,6 +16,10 @@ pub fn main(r: &mut Runner, input: &[u8]) {
     r.part("Part 2", || maze.count_cheats_p2(100));
     r.set_tail("Part 1");
     r.part("Part 2 (Manhattan)", || maze.count_cheats_p2_manhattan(100));
+    r.set_tail("Parse");
+    r.part("Part 2 (Distance Map)", || {
+        maze.count_cheats_distance_map(20, 100)
+    });
 
     r.info_debug("Maze Size", &maze.grid.size());
     r.info_debug("Maze Start", &maze.start_pos);
@@ -155,6 +159,14 @@ impl Maze {
             .sum::<usize>()
     }
 
+    fn count_cheats_distance_map(&self, radius: usize, min: u32) -> usize {
+        let distances = self.grid.distance_map([self.end_pos], |c| *c != WALL);
+        distances
+            .pairs_within(radius)
+            .filter(|(a, b, d)| distances[*a] >= distances[*b] + d + min)
+            .count()
+    }
+
     fn with_distances(&self) -> Self {
         let mut search = bfs().with_seen_space(FxHashSet::default());
         search.push((self.end_pos, 0));
@@ -279,6 +291,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn distance_map_works_on_example() {
+        let maze = Maze::parse(EXAMPLE);
+        assert_eq!(maze.count_cheats_distance_map(2, 10), 10);
+        assert_eq!(maze.count_cheats_distance_map(20, 70), 12 + 22 + 4 + 3);
+        assert_eq!(maze.count_cheats_distance_map(20, 50), 285);
+    }
+
     #[test]
     fn part_2_manhattan_works_on_example() {
         assert_eq!(
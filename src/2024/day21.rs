@@ -1,6 +1,7 @@
 use common::grid::Grid;
 use common::runner::Runner;
 use common::search::{bfs, Cost, Key, Order};
+use common::utils::RobotChain;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 const UP: u8 = b'^';
@@ -18,6 +19,9 @@ pub fn main(r: &mut Runner, input: &[u8]) {
     r.set_tail("Parse");
     r.part("Part 1 (Recursive)", || part_1_recursive(&codes));
     r.part("Part 2 (Recursive)", || part_2_recursive(&codes));
+    r.set_tail("Parse");
+    r.part("Part 1 (Keypad)", || run_keypad(&codes, 2));
+    r.part("Part 2 (Keypad)", || run_keypad(&codes, 25));
 }
 
 type Keypad = Grid<(u8, u8), [u8; 30], u8>;
@@ -43,6 +47,18 @@ fn part_2_recursive(codes: &[[u8; 4]]) -> u64 {
         .sum()
 }
 
+fn run_keypad(codes: &[[u8; 4]], layers: usize) -> u64 {
+    let mut chain = RobotChain::new(
+        common::utils::Keypad::numeric(),
+        common::utils::Keypad::directional(),
+        layers,
+    );
+    codes
+        .iter()
+        .map(|code| chain.cost(code).expect("no way to type the code") * code_number(*code))
+        .sum()
+}
+
 fn run_recursive<const ROBOTS: usize>(code: &[u8]) -> u64 {
     run_recursive_step::<ROBOTS>(0, code, &mut FxHashMap::default())
 }
@@ -449,6 +465,13 @@ mod tests {
         assert_eq!(part_1_recursive(&parse(EXAMPLE)), 126384)
     }
 
+    #[test]
+    fn keypad_matches_recursive() {
+        let codes = parse(EXAMPLE);
+        assert_eq!(run_keypad(&codes, 2), 126384);
+        assert_eq!(run_keypad(&codes, 25), part_2_recursive(&codes));
+    }
+
     #[test]
     fn pathfinder_works_on_part1_examples() {
         assert_eq!(run_pathfinding::<1>(b"029A"), 12);
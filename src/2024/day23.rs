@@ -1,9 +1,9 @@
 use arrayvec::ArrayVec;
-use common::graph::Graph;
+use common::graph::IndexedGraph;
 use common::runner::Runner;
 use rustc_hash::FxHashSet;
 
-type LanGraph = Graph<[u8; 2], (), 16>;
+type LanGraph = IndexedGraph<[u8; 2], (), 16>;
 
 pub fn main(r: &mut Runner, input: &[u8]) {
     let graph = r.prep("Parse", || parse(&input));
@@ -11,6 +11,9 @@ pub fn main(r: &mut Runner, input: &[u8]) {
     r.part("Part 1", || part_1(&graph));
     r.part("Part 2", || part_2(&graph));
 
+    r.set_tail("Parse");
+    r.part("Part 2 (Bron-Kerbosch)", || part_2_max_clique(&graph));
+
     r.info("Computers", &graph.len());
     r.info("Connections", &graph.edge_count());
 }
@@ -109,6 +112,17 @@ fn part_2(graph: &LanGraph) -> String {
     res
 }
 
+fn part_2_max_clique(graph: &LanGraph) -> String {
+    let mut computers: Vec<String> = graph
+        .max_clique()
+        .into_iter()
+        .map(|i| String::from_utf8_lossy(graph.node(i)).into_owned())
+        .collect();
+    computers.sort_unstable();
+
+    computers.join(",")
+}
+
 fn parse(input: &[u8]) -> LanGraph {
     input
         .iter()
@@ -164,4 +178,10 @@ td-yn
         let graph = parse(EXAMPLE);
         assert_eq!(part_2(&graph).as_str(), "co,de,ka,ta");
     }
+
+    #[test]
+    fn part2_max_clique_works_on_example() {
+        let graph = parse(EXAMPLE);
+        assert_eq!(part_2_max_clique(&graph).as_str(), "co,de,ka,ta");
+    }
 }
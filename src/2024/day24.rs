@@ -1,4 +1,5 @@
 use arrayvec::ArrayVec;
+use common::circuit::Circuit;
 use common::parser;
 use common::parser::Parser;
 use common::runner::Runner;
@@ -10,6 +11,11 @@ pub fn main(r: &mut Runner, input: &[u8]) {
     r.part("Part 1", || part_1(&wires, x, y));
     r.part("Part 2", || part_2(&wires));
     r.info("Gates", &wires.map.len());
+
+    r.set_tail("Parse");
+    let (circuit, inputs) = r.prep("Parse (Circuit)", || parse_circuit(input));
+    r.part("Part 1 (Circuit)", || part_1_circuit(&circuit, &inputs));
+    r.part("Part 2 (Circuit)", || part_2_circuit(&circuit));
 }
 
 fn part_1(wires: &Wires, x: u64, y: u64) -> u64 {
@@ -94,6 +100,42 @@ fn part_2(wires: &Wires) -> String {
     res
 }
 
+/// The circuit, and the initial values of its inputs in every lane.
+fn parse_circuit(input: &[u8]) -> (Circuit, Vec<(usize, u64)>) {
+    let circuit: Circuit = std::str::from_utf8(input).unwrap().parse().unwrap();
+    let inputs = input
+        .split(|b| *b == b'\n')
+        .take_while(|line| !line.is_empty())
+        .map(|line| {
+            let name = std::str::from_utf8(&line[..line.len() - 3]).unwrap();
+            let value = if line[line.len() - 1] == b'1' {
+                u64::MAX
+            } else {
+                0
+            };
+            (circuit.wire_index(name).unwrap(), value)
+        })
+        .collect();
+
+    (circuit, inputs)
+}
+
+fn part_1_circuit(circuit: &Circuit, inputs: &[(usize, u64)]) -> u64 {
+    let values = circuit.simulate(inputs.iter().copied()).unwrap();
+    Circuit::read_bus(&values, &circuit.bus("z"), 0)
+}
+
+fn part_2_circuit(circuit: &Circuit) -> String {
+    let mut names: Vec<&str> = circuit
+        .check_ripple_carry_adder("x", "y", "z")
+        .into_iter()
+        .map(|f| circuit.name(f.wire))
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    names.join(",")
+}
+
 fn parse(input: &[u8]) -> (u64, u64, Wires) {
     xy_parser()
         .and_discard(b'\n')
@@ -354,4 +396,47 @@ tnw OR pbm -> gnj
         let (x, y, wires) = parse(EXAMPLE_2);
         assert_eq!(part_1(&wires, x, y), 2024);
     }
+
+    #[test]
+    fn circuit_matches_wires() {
+        let (circuit, inputs) = parse_circuit(EXAMPLE_1);
+        assert_eq!(part_1_circuit(&circuit, &inputs), 4);
+        let (circuit, inputs) = parse_circuit(EXAMPLE_2);
+        assert_eq!(part_1_circuit(&circuit, &inputs), 2024);
+    }
+
+    /// A 4-bit ripple-carry adder with `z02`/`t02` and `h03`/`a03` swapped.
+    const EXAMPLE_SWAPPED: &[u8] = b"x00: 1
+x01: 0
+x02: 1
+x03: 1
+y00: 1
+y01: 1
+y02: 0
+y03: 1
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> h01
+h01 XOR c00 -> z01
+x01 AND y01 -> a01
+h01 AND c00 -> t01
+a01 OR t01 -> c01
+x02 XOR y02 -> h02
+h02 XOR c01 -> t02
+x02 AND y02 -> a02
+h02 AND c01 -> z02
+a02 OR t02 -> c02
+x03 XOR y03 -> a03
+h03 XOR c02 -> z03
+x03 AND y03 -> h03
+h03 AND c02 -> t03
+a03 OR t03 -> z04
+";
+
+    #[test]
+    fn part_2_circuit_finds_swapped_outputs() {
+        let (circuit, _) = parse_circuit(EXAMPLE_SWAPPED);
+        assert_eq!(part_2_circuit(&circuit), "a03,h03,t02,z02");
+    }
 }
@@ -0,0 +1,128 @@
+use super::{Circuit, GateKind};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FaultKind {
+    /// An output bit that isn't driven by a XOR.
+    OutputNotSum,
+    /// The last output bit should be the final carry, from an OR.
+    CarryOutNotOr,
+    /// A XOR that doesn't read the inputs, but doesn't drive an output either.
+    StrayXor,
+    /// The half-sum of an input pair that no XOR picks up.
+    HalfSumNotSummed,
+    /// A carry from an AND that doesn't go into an OR.
+    CarryNotMerged,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct AdderFault {
+    pub wire: usize,
+    pub kind: FaultKind,
+}
+
+impl Circuit {
+    /// Check that the circuit is shaped like a ripple-carry adder, adding the `a` and `b`
+    /// buses into `out`. Every wire that breaks the pattern gets reported, which for day 24
+    /// is exactly the swapped outputs. A wire can show up more than once. Bit 0 is a half
+    /// adder and has no carry in.
+    pub fn check_ripple_carry_adder(&self, a: &str, b: &str, out: &str) -> Vec<AdderFault> {
+        let a_bus = self.bus(a);
+        let b_bus = self.bus(b);
+        let out_bus = self.bus(out);
+        let is_input = |w: usize| a_bus.contains(&w) || b_bus.contains(&w);
+        let first_pair = |w: usize| a_bus.first() == Some(&w) || b_bus.first() == Some(&w);
+        let read_by = |w: usize, kind: GateKind| self.fan_out(w).any(|g| g.kind == kind);
+
+        let mut faults = Vec::new();
+        let mut fault = |wire, kind| faults.push(AdderFault { wire, kind });
+
+        for (i, wire) in out_bus.iter().copied().enumerate() {
+            let kind = self.driver(wire).map(|g| g.kind);
+            if i + 1 == out_bus.len() && out_bus.len() > a_bus.len() {
+                if kind != Some(GateKind::Or) {
+                    fault(wire, FaultKind::CarryOutNotOr);
+                }
+            } else if kind != Some(GateKind::Xor) {
+                fault(wire, FaultKind::OutputNotSum);
+            }
+        }
+
+        for gate in self.gates() {
+            let from_inputs = gate.inputs().iter().all(|w| is_input(*w));
+            let from_first = gate.inputs().iter().all(|w| first_pair(*w));
+            match gate.kind {
+                GateKind::Xor if !from_inputs && !out_bus.contains(&gate.output) => {
+                    fault(gate.output, FaultKind::StrayXor);
+                }
+                GateKind::Xor
+                    if from_inputs && !from_first && !read_by(gate.output, GateKind::Xor) =>
+                {
+                    fault(gate.output, FaultKind::HalfSumNotSummed);
+                }
+                GateKind::And if !from_first && !read_by(gate.output, GateKind::Or) => {
+                    fault(gate.output, FaultKind::CarryNotMerged);
+                }
+                _ => {}
+            }
+        }
+
+        faults
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adder(bits: usize) -> String {
+        let mut res = String::from("x00 XOR y00 -> z00\nx00 AND y00 -> c00\n");
+        for i in 1..bits {
+            let p = i - 1;
+            res += &format!("x{i:02} XOR y{i:02} -> h{i:02}\n");
+            res += &format!("h{i:02} XOR c{p:02} -> z{i:02}\n");
+            res += &format!("x{i:02} AND y{i:02} -> a{i:02}\n");
+            res += &format!("h{i:02} AND c{p:02} -> t{i:02}\n");
+            let carry = if i + 1 == bits {
+                format!("z{bits:02}")
+            } else {
+                format!("c{i:02}")
+            };
+            res += &format!("a{i:02} OR t{i:02} -> {carry}\n");
+        }
+
+        res
+    }
+
+    #[test]
+    fn adds_and_passes() {
+        let circuit: Circuit = adder(6).parse().unwrap();
+        assert_eq!(circuit.check_ripple_carry_adder("x", "y", "z"), vec![]);
+
+        let (x, y, z) = (circuit.bus("x"), circuit.bus("y"), circuit.bus("z"));
+        let values = circuit
+            .simulate(Circuit::bus_inputs(&x, 45).chain(Circuit::bus_inputs(&y, 38)))
+            .unwrap();
+        assert_eq!(Circuit::read_bus(&values, &z, 17), 83);
+    }
+
+    #[test]
+    fn finds_swaps() {
+        let source = adder(6)
+            .replace("-> z03", "-> SWAP")
+            .replace("-> t03", "-> z03")
+            .replace("-> SWAP", "-> t03")
+            .replace("-> h05", "-> SWAP")
+            .replace("-> a05", "-> h05")
+            .replace("-> SWAP", "-> a05");
+        let circuit: Circuit = source.parse().unwrap();
+
+        let mut names: Vec<&str> = circuit
+            .check_ripple_carry_adder("x", "y", "z")
+            .into_iter()
+            .map(|f| circuit.name(f.wire))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names, vec!["a05", "h05", "t03", "z03"]);
+    }
+}
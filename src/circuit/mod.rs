@@ -0,0 +1,382 @@
+mod adder;
+
+use rustc_hash::FxHashMap;
+use std::str::FromStr;
+
+pub use adder::{AdderFault, FaultKind};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GateKind {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+impl GateKind {
+    #[inline]
+    pub fn apply(&self, a: u64, b: u64) -> u64 {
+        match self {
+            GateKind::And => a & b,
+            GateKind::Or => a | b,
+            GateKind::Xor => a ^ b,
+            GateKind::Not => !a,
+        }
+    }
+}
+
+/// A gate reading one or two wires. `Not` only uses the first input.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Gate {
+    pub kind: GateKind,
+    pub inputs: [usize; 2],
+    pub output: usize,
+}
+
+impl Gate {
+    #[inline]
+    pub fn inputs(&self) -> &[usize] {
+        match self.kind {
+            GateKind::Not => &self.inputs[..1],
+            _ => &self.inputs,
+        }
+    }
+}
+
+/// The wires that couldn't be ordered, because they're on or behind a cycle.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CycleError(pub Vec<usize>);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseCircuitError {
+    BadLine(usize),
+    /// The wire on this line already has a gate driving it.
+    DrivenTwice(usize),
+}
+
+/// A netlist of named wires and the gates between them. Wires are interned into indices
+/// when they're first mentioned.
+#[derive(Debug, Clone, Default)]
+pub struct Circuit {
+    names: Vec<String>,
+    index: FxHashMap<String, usize>,
+    drivers: Vec<Option<usize>>,
+    readers: Vec<Vec<usize>>,
+    gates: Vec<Gate>,
+}
+
+impl Circuit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the index of the wire, adding it if it's new.
+    pub fn wire(&mut self, name: &str) -> usize {
+        if let Some(index) = self.index.get(name) {
+            return *index;
+        }
+
+        let index = self.names.len();
+        self.names.push(name.to_owned());
+        self.index.insert(name.to_owned(), index);
+        self.drivers.push(None);
+        self.readers.push(Vec::new());
+        index
+    }
+
+    #[inline]
+    pub fn wire_index(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    #[inline]
+    pub fn name(&self, wire: usize) -> &str {
+        &self.names[wire]
+    }
+
+    #[inline]
+    pub fn wire_count(&self) -> usize {
+        self.names.len()
+    }
+
+    #[inline]
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// Add a gate, returning its index, or `None` if the output already has a driver.
+    pub fn add_gate(&mut self, kind: GateKind, a: &str, b: &str, output: &str) -> Option<usize> {
+        let inputs = [self.wire(a), self.wire(b)];
+        let output = self.wire(output);
+        if self.drivers[output].is_some() {
+            return None;
+        }
+
+        let index = self.gates.len();
+        self.gates.push(Gate {
+            kind,
+            inputs,
+            output,
+        });
+        self.drivers[output] = Some(index);
+        for (i, input) in self.gates[index].inputs().to_vec().into_iter().enumerate() {
+            // `x XOR x` reads x once as far as the readers go.
+            if i == 0 || input != inputs[0] {
+                self.readers[input].push(index);
+            }
+        }
+
+        Some(index)
+    }
+
+    pub fn add_not(&mut self, a: &str, output: &str) -> Option<usize> {
+        self.add_gate(GateKind::Not, a, a, output)
+    }
+
+    /// The gate driving the wire, if it isn't an input.
+    #[inline]
+    pub fn driver(&self, wire: usize) -> Option<&Gate> {
+        self.drivers[wire].map(|g| &self.gates[g])
+    }
+
+    /// The gates that read the wire.
+    #[inline]
+    pub fn fan_out(&self, wire: usize) -> impl Iterator<Item = &Gate> + '_ {
+        self.readers[wire].iter().map(|g| &self.gates[*g])
+    }
+
+    /// The wires the wire's driver reads. Inputs have none.
+    #[inline]
+    pub fn fan_in(&self, wire: usize) -> &[usize] {
+        self.driver(wire).map(|g| g.inputs()).unwrap_or(&[])
+    }
+
+    /// Every wire that the wire depends on, directly or not.
+    pub fn fan_in_cone(&self, wire: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.wire_count()];
+        let mut stack = vec![wire];
+        let mut cone = Vec::new();
+        while let Some(current) = stack.pop() {
+            for input in self.fan_in(current) {
+                if !seen[*input] {
+                    seen[*input] = true;
+                    cone.push(*input);
+                    stack.push(*input);
+                }
+            }
+        }
+
+        cone.sort_unstable();
+        cone
+    }
+
+    /// Wires without a driver.
+    pub fn inputs(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.wire_count()).filter(|w| self.drivers[*w].is_none())
+    }
+
+    /// Wires that no gate reads.
+    pub fn outputs(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.wire_count()).filter(|w| self.readers[*w].is_empty())
+    }
+
+    /// The wires named `prefix` followed by a number, ordered by that number, like the
+    /// `x00`, `x01`, ... of day 24.
+    pub fn bus(&self, prefix: &str) -> Vec<usize> {
+        let mut bus: Vec<(u32, usize)> = self
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(i, name)| Some((name.strip_prefix(prefix)?.parse().ok()?, i)))
+            .collect();
+        bus.sort_unstable();
+        bus.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// The gates in an order where every gate comes after the ones it reads from.
+    pub fn topological_order(&self) -> Result<Vec<usize>, CycleError> {
+        let mut pending: Vec<usize> = self
+            .gates
+            .iter()
+            .map(|g| {
+                g.inputs()
+                    .iter()
+                    .filter(|w| self.drivers[**w].is_some())
+                    .count()
+            })
+            .collect();
+        let mut ready: Vec<usize> = (0..self.gates.len()).filter(|g| pending[*g] == 0).collect();
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(gate) = ready.pop() {
+            order.push(gate);
+            for reader in self.readers[self.gates[gate].output].iter() {
+                let inputs = self.gates[*reader].inputs();
+                pending[*reader] -= inputs
+                    .iter()
+                    .filter(|w| **w == self.gates[gate].output)
+                    .count();
+                if pending[*reader] == 0 {
+                    ready.push(*reader);
+                }
+            }
+        }
+
+        if order.len() == self.gates.len() {
+            Ok(order)
+        } else {
+            let mut stuck: Vec<usize> = (0..self.gates.len())
+                .filter(|g| pending[*g] > 0)
+                .map(|g| self.gates[g].output)
+                .collect();
+            stuck.sort_unstable();
+            Err(CycleError(stuck))
+        }
+    }
+
+    /// Run the gates in `order` over the wire values, 64 independent lanes at a time.
+    /// The inputs need to be set beforehand.
+    pub fn evaluate(&self, order: &[usize], values: &mut [u64]) {
+        for gate in order.iter().map(|g| &self.gates[*g]) {
+            let [a, b] = gate.inputs;
+            values[gate.output] = gate.kind.apply(values[a], values[b]);
+        }
+    }
+
+    /// Set the inputs and evaluate, returning the value of every wire.
+    pub fn simulate<I>(&self, inputs: I) -> Result<Vec<u64>, CycleError>
+    where
+        I: IntoIterator<Item = (usize, u64)>,
+    {
+        let order = self.topological_order()?;
+        let mut values = vec![0; self.wire_count()];
+        for (wire, value) in inputs {
+            values[wire] = value;
+        }
+
+        self.evaluate(&order, &mut values);
+        Ok(values)
+    }
+
+    /// Put the number on the bus in every lane, for `simulate`.
+    pub fn bus_inputs(bus: &[usize], value: u64) -> impl Iterator<Item = (usize, u64)> + '_ {
+        bus.iter()
+            .enumerate()
+            .map(move |(i, wire)| (*wire, if value >> i & 1 == 1 { u64::MAX } else { 0 }))
+    }
+
+    /// Read the number on the bus in one lane.
+    pub fn read_bus(values: &[u64], bus: &[usize], lane: u32) -> u64 {
+        bus.iter()
+            .enumerate()
+            .fold(0, |acc, (i, wire)| acc | (values[*wire] >> lane & 1) << i)
+    }
+}
+
+/// Reads `a AND b -> c`, `a OR b -> c`, `a XOR b -> c` and `NOT a -> c` lines. Blank
+/// lines and `name: value` lines are skipped, so a whole day 24 input goes in as-is.
+impl FromStr for Circuit {
+    type Err = ParseCircuitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut circuit = Circuit::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.contains(':') {
+                continue;
+            }
+
+            let (lhs, output) = line
+                .split_once(" -> ")
+                .ok_or(ParseCircuitError::BadLine(i + 1))?;
+            let tokens: Vec<&str> = lhs.split_whitespace().collect();
+            let added = match tokens.as_slice() {
+                ["NOT", a] => circuit.add_not(a, output),
+                [a, op, b] => {
+                    let kind = match *op {
+                        "AND" => GateKind::And,
+                        "OR" => GateKind::Or,
+                        "XOR" => GateKind::Xor,
+                        _ => return Err(ParseCircuitError::BadLine(i + 1)),
+                    };
+                    circuit.add_gate(kind, a, b, output)
+                }
+                _ => return Err(ParseCircuitError::BadLine(i + 1)),
+            };
+
+            if added.is_none() {
+                return Err(ParseCircuitError::DrivenTwice(i + 1));
+            }
+        }
+
+        Ok(circuit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_in_lanes() {
+        let circuit: Circuit =
+            "x00: 1\n\nx00 AND y00 -> z00\nx01 XOR y01 -> z01\nNOT x00 -> nx\nnx OR y01 -> z02\n"
+                .parse()
+                .unwrap();
+        let (x, y, z) = (circuit.bus("x"), circuit.bus("y"), circuit.bus("z"));
+        assert_eq!(z.len(), 3);
+
+        let values = circuit
+            .simulate(Circuit::bus_inputs(&x, 0b11).chain([(y[0], 0b01), (y[1], 0b10)]))
+            .unwrap();
+        assert_eq!(Circuit::read_bus(&values, &z, 0), 0b011);
+        assert_eq!(Circuit::read_bus(&values, &z, 1), 0b100);
+
+        let nx = circuit.wire_index("nx").unwrap();
+        assert_eq!(circuit.fan_in(nx), &[x[0]]);
+        assert_eq!(circuit.fan_out(nx).count(), 1);
+        assert_eq!(
+            circuit.fan_in_cone(z[2]),
+            vec![x[0], circuit.wire_index("y01").unwrap(), nx]
+        );
+        assert_eq!(circuit.inputs().count(), 4);
+        assert_eq!(circuit.outputs().collect::<Vec<_>>(), z);
+    }
+
+    #[test]
+    fn detects_cycles_and_bad_lines() {
+        let circuit: Circuit = "a AND b -> c\nc OR d -> e\ne XOR a -> d\n".parse().unwrap();
+        let names: Vec<&str> = circuit
+            .topological_order()
+            .unwrap_err()
+            .0
+            .into_iter()
+            .map(|w| circuit.name(w))
+            .collect();
+        assert_eq!(names, vec!["d", "e"]);
+
+        assert_eq!(
+            "a NAND b -> c".parse::<Circuit>().unwrap_err(),
+            ParseCircuitError::BadLine(1)
+        );
+        assert_eq!(
+            "a OR b -> c\na AND b -> c".parse::<Circuit>().unwrap_err(),
+            ParseCircuitError::DrivenTwice(2)
+        );
+    }
+
+    #[test]
+    fn repeated_inputs() {
+        let circuit: Circuit = "a AND b -> x\nx XOR x -> y\nx OR c -> z\n".parse().unwrap();
+        let (x, y) = (
+            circuit.wire_index("x").unwrap(),
+            circuit.wire_index("y").unwrap(),
+        );
+        assert_eq!(circuit.fan_out(x).count(), 2);
+
+        let values = circuit
+            .simulate([(circuit.wire_index("a").unwrap(), 1)])
+            .unwrap();
+        assert_eq!(values[y], 0);
+        assert_eq!(circuit.topological_order().unwrap().len(), 3);
+    }
+}
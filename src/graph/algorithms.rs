@@ -0,0 +1,431 @@
+use super::{EdgeList, Graph};
+use crate::utils::UnionFind;
+use bit_vec::BitVec;
+use num::Zero;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::Add;
+
+impl<N, E, const CAP: usize, L, I> Graph<N, E, CAP, L, I>
+where
+    L: EdgeList<E>,
+{
+    /// Tarjan's algorithm. The components come out in reverse topological order, so
+    /// a component never has edges into one that comes after it.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let len = self.nodes.len();
+        let mut indices = vec![usize::MAX; len];
+        let mut low_links = vec![0; len];
+        let mut on_stack = BitVec::from_elem(len, false);
+        let mut stack = Vec::with_capacity(len);
+        let mut call_stack: Vec<(usize, usize)> = Vec::with_capacity(len);
+        let mut next_index = 0;
+        let mut components = Vec::new();
+
+        for root in 0..len {
+            if indices[root] != usize::MAX {
+                continue;
+            }
+
+            indices[root] = next_index;
+            low_links[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack.set(root, true);
+            call_stack.push((root, 0));
+
+            while let Some((v, edge_index)) = call_stack.last().copied() {
+                if let Some((w, _)) = self.edges[v].as_slice().get(edge_index) {
+                    let w = *w;
+                    call_stack.last_mut().unwrap().1 += 1;
+
+                    if indices[w] == usize::MAX {
+                        indices[w] = next_index;
+                        low_links[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack.set(w, true);
+                        call_stack.push((w, 0));
+                    } else if on_stack[w] {
+                        low_links[v] = low_links[v].min(indices[w]);
+                    }
+                } else {
+                    call_stack.pop();
+                    if let Some((parent, _)) = call_stack.last() {
+                        low_links[*parent] = low_links[*parent].min(low_links[v]);
+                    }
+
+                    if low_links[v] == indices[v] {
+                        let mut component = Vec::new();
+                        while let Some(w) = stack.pop() {
+                            on_stack.set(w, false);
+                            component.push(w);
+                            if w == v {
+                                break;
+                            }
+                        }
+
+                        components.push(component);
+                    }
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Kahn's algorithm, picking the lowest index first when there's a choice. If the
+    /// graph has a cycle, one of them is returned as the error in edge order, starting
+    /// from its lowest index.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Vec<usize>> {
+        let len = self.nodes.len();
+        let mut in_degrees = vec![0usize; len];
+        for edges in self.edges.iter() {
+            for (dst, _) in edges.as_slice().iter() {
+                in_degrees[*dst] += 1;
+            }
+        }
+
+        let mut queue: BinaryHeap<Reverse<usize>> = (0..len)
+            .filter(|i| in_degrees[*i] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = Vec::with_capacity(len);
+        while let Some(Reverse(current)) = queue.pop() {
+            order.push(current);
+            for (dst, _) in self.edges[current].as_slice().iter() {
+                in_degrees[*dst] -= 1;
+                if in_degrees[*dst] == 0 {
+                    queue.push(Reverse(*dst));
+                }
+            }
+        }
+
+        if order.len() == len {
+            return Ok(order);
+        }
+
+        // Every node left over has a predecessor that's also left over, so walking
+        // backwards from any of them must end up in a loop.
+        let mut predecessors = vec![usize::MAX; len];
+        for (src, edges) in self.edges.iter().enumerate() {
+            if in_degrees[src] == 0 {
+                continue;
+            }
+            for (dst, _) in edges.as_slice().iter() {
+                if in_degrees[*dst] > 0 {
+                    predecessors[*dst] = src;
+                }
+            }
+        }
+
+        let mut visited_at = vec![usize::MAX; len];
+        let mut path = Vec::new();
+        let mut current = (0..len).find(|i| in_degrees[*i] > 0).unwrap();
+        while visited_at[current] == usize::MAX {
+            visited_at[current] = path.len();
+            path.push(current);
+            current = predecessors[current];
+        }
+
+        let mut cycle = path.split_off(visited_at[current]);
+        cycle.reverse();
+        let lowest = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+        cycle.rotate_left(lowest);
+        Err(cycle)
+    }
+
+    /// Find the connected components, ignoring edge directions.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let len = self.nodes.len();
+        let mut uf = UnionFind::new(len);
+        for (src, edges) in self.edges.iter().enumerate() {
+            for (dst, _) in edges.as_slice().iter() {
+                uf.union(src, *dst);
+            }
+        }
+
+        uf.groups()
+    }
+
+    /// Bron–Kerbosch with pivoting. Edges are treated as undirected. The clique is
+    /// returned as sorted node indices.
+    pub fn max_clique(&self) -> Vec<usize> {
+        let len = self.nodes.len();
+        let mut neighbors = vec![BitVec::from_elem(len, false); len];
+        for (src, edges) in self.edges.iter().enumerate() {
+            for (dst, _) in edges.as_slice().iter() {
+                if src != *dst {
+                    neighbors[src].set(*dst, true);
+                    neighbors[*dst].set(src, true);
+                }
+            }
+        }
+
+        let mut best = Vec::new();
+        let mut current = Vec::with_capacity(16);
+        bron_kerbosch(
+            &neighbors,
+            &mut current,
+            BitVec::from_elem(len, true),
+            BitVec::from_elem(len, false),
+            &mut best,
+        );
+
+        best.sort_unstable();
+        best
+    }
+
+    /// Stoer–Wagner global minimum cut, treating edges as undirected. An edge and its
+    /// mirror (from `connect_mutual`) count as one. Returns the weight of the cut and
+    /// the nodes on one side of it, or `None` if there are fewer than two nodes.
+    pub fn min_cut<W, F>(&self, weight: F) -> Option<(W, Vec<usize>)>
+    where
+        W: Copy + Ord + Zero + Add<Output = W>,
+        F: Fn(&E) -> W,
+    {
+        let len = self.nodes.len();
+        if len < 2 {
+            return None;
+        }
+
+        let mut matrix = vec![vec![W::zero(); len]; len];
+        for (src, edges) in self.edges.iter().enumerate() {
+            for (dst, e) in edges.as_slice().iter() {
+                let dst = *dst;
+                if src == dst
+                    || (dst < src && self.edges[dst].as_slice().iter().any(|(d, _)| *d == src))
+                {
+                    continue;
+                }
+
+                let w = weight(e);
+                matrix[src][dst] = matrix[src][dst] + w;
+                matrix[dst][src] = matrix[dst][src] + w;
+            }
+        }
+
+        let mut members: Vec<Vec<usize>> = (0..len).map(|i| vec![i]).collect();
+        let mut active: Vec<usize> = (0..len).collect();
+        let mut best: Option<(W, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            let mut added = vec![false; len];
+            let mut weights = vec![W::zero(); len];
+            let mut prev = active[0];
+            let mut last = active[0];
+
+            for _ in 0..active.len() {
+                let next = active
+                    .iter()
+                    .copied()
+                    .filter(|i| !added[*i])
+                    .max_by_key(|i| weights[*i])
+                    .unwrap();
+
+                added[next] = true;
+                prev = last;
+                last = next;
+
+                for i in active.iter().copied() {
+                    if !added[i] {
+                        weights[i] = weights[i] + matrix[next][i];
+                    }
+                }
+            }
+
+            let cut_weight = weights[last];
+            if best.as_ref().is_none_or(|(w, _)| cut_weight < *w) {
+                best = Some((cut_weight, members[last].clone()));
+            }
+
+            let moved = std::mem::take(&mut members[last]);
+            members[prev].extend(moved);
+            let merged: Vec<W> = matrix[prev]
+                .iter()
+                .zip(matrix[last].iter())
+                .map(|(a, b)| *a + *b)
+                .collect();
+            for (row, w) in matrix.iter_mut().zip(merged.iter()) {
+                row[prev] = *w;
+            }
+            matrix[prev] = merged;
+            active.retain(|i| *i != last);
+        }
+
+        best.map(|(w, mut side)| {
+            side.sort_unstable();
+            (w, side)
+        })
+    }
+}
+
+fn bron_kerbosch(
+    neighbors: &[BitVec],
+    current: &mut Vec<usize>,
+    mut candidates: BitVec,
+    mut excluded: BitVec,
+    best: &mut Vec<usize>,
+) {
+    if candidates.none() {
+        if excluded.none() && current.len() > best.len() {
+            *best = current.clone();
+        }
+        return;
+    }
+    if current.len() + candidates.count_ones() as usize <= best.len() {
+        return;
+    }
+
+    let pivot = candidates
+        .iter()
+        .zip(excluded.iter())
+        .enumerate()
+        .filter(|(_, (c, x))| *c || *x)
+        .map(|(i, _)| i)
+        .max_by_key(|i| {
+            let mut shared = candidates.clone();
+            shared.and(&neighbors[*i]);
+            shared.count_ones()
+        })
+        .unwrap();
+
+    let mut remaining = candidates.clone();
+    remaining.difference(&neighbors[pivot]);
+
+    for v in (0..remaining.len()).filter(|i| remaining[*i]) {
+        let mut next_candidates = candidates.clone();
+        next_candidates.and(&neighbors[v]);
+        let mut next_excluded = excluded.clone();
+        next_excluded.and(&neighbors[v]);
+
+        current.push(v);
+        bron_kerbosch(neighbors, current, next_candidates, next_excluded, best);
+        current.pop();
+
+        candidates.set(v, false);
+        excluded.set(v, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directed(len: usize, edges: &[(usize, usize)]) -> Graph<usize, (), 8> {
+        let mut graph = Graph::new();
+        for i in 0..len {
+            graph.add_node(i);
+        }
+        for (src, dst) in edges.iter() {
+            graph.connect(*src, *dst, ());
+        }
+
+        graph
+    }
+
+    fn undirected(len: usize, edges: &[(usize, usize, u32)]) -> Graph<usize, u32, 8> {
+        let mut graph = Graph::new();
+        for i in 0..len {
+            graph.add_node(i);
+        }
+        for (src, dst, w) in edges.iter() {
+            graph.connect_mutual(*src, *dst, *w);
+        }
+
+        graph
+    }
+
+    #[test]
+    fn scc_finds_components() {
+        let graph = directed(
+            8,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (5, 3),
+                (6, 5),
+                (6, 7),
+                (7, 6),
+            ],
+        );
+
+        let mut components = graph.strongly_connected_components();
+        for component in components.iter_mut() {
+            component.sort_unstable();
+        }
+
+        assert_eq!(components, vec![vec![3, 4, 5], vec![0, 1, 2], vec![6, 7]]);
+    }
+
+    #[test]
+    fn topological_sort_orders_dag() {
+        let graph = directed(6, &[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]);
+        assert_eq!(graph.topological_sort(), Ok(vec![4, 5, 0, 2, 3, 1]));
+    }
+
+    #[test]
+    fn topological_sort_reports_cycle() {
+        let graph = directed(5, &[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert_eq!(graph.topological_sort(), Err(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn connected_components_ignore_direction() {
+        let graph = directed(6, &[(1, 0), (2, 1), (4, 3)]);
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn max_clique_finds_largest() {
+        let graph = undirected(
+            7,
+            &[
+                (0, 1, 1),
+                (0, 2, 1),
+                (1, 2, 1),
+                (2, 3, 1),
+                (3, 4, 1),
+                (3, 5, 1),
+                (3, 6, 1),
+                (4, 5, 1),
+                (4, 6, 1),
+                (5, 6, 1),
+            ],
+        );
+
+        assert_eq!(graph.max_clique(), vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn min_cut_splits_two_clusters() {
+        // The classic example from the Stoer–Wagner paper.
+        let graph = undirected(
+            8,
+            &[
+                (0, 1, 2),
+                (0, 4, 3),
+                (1, 2, 3),
+                (1, 4, 2),
+                (1, 5, 2),
+                (2, 3, 4),
+                (2, 6, 2),
+                (3, 6, 2),
+                (3, 7, 2),
+                (4, 5, 3),
+                (5, 6, 1),
+                (6, 7, 3),
+            ],
+        );
+
+        assert_eq!(graph.min_cut(|w| *w), Some((4, vec![0, 1, 4, 5])));
+        assert_eq!(directed(1, &[]).min_cut(|_| 1u32), None);
+    }
+}
@@ -0,0 +1,156 @@
+use super::{LinearNodeIndex, NodeIndex};
+
+/// A graph in compressed sparse row form. Every edge lives in one list, with each node
+/// owning a range of it. It can't grow, but it's compact and fast to walk. It keeps the
+/// node index of the graph it was frozen from.
+pub struct FrozenGraph<N, E, I = LinearNodeIndex> {
+    nodes: Vec<N>,
+    index: I,
+    offsets: Vec<usize>,
+    edges: Vec<(usize, E)>,
+    incoming_offsets: Vec<usize>,
+    incoming: Vec<usize>,
+}
+
+impl<N, E, I> FrozenGraph<N, E, I>
+where
+    E: Clone,
+{
+    pub(super) fn new<'a, IE, II>(nodes: Vec<N>, index: I, edges: IE, incoming: II) -> Self
+    where
+        E: 'a,
+        IE: Iterator<Item = &'a [(usize, E)]>,
+        II: Iterator<Item = &'a [usize]>,
+    {
+        let mut offsets = Vec::with_capacity(nodes.len() + 1);
+        let mut edge_list = Vec::new();
+        offsets.push(0);
+        for list in edges {
+            edge_list.extend_from_slice(list);
+            offsets.push(edge_list.len());
+        }
+
+        let mut incoming_offsets = Vec::with_capacity(nodes.len() + 1);
+        let mut incoming_list = Vec::with_capacity(edge_list.len());
+        incoming_offsets.push(0);
+        for list in incoming {
+            incoming_list.extend_from_slice(list);
+            incoming_offsets.push(incoming_list.len());
+        }
+
+        Self {
+            nodes,
+            index,
+            offsets,
+            edges: edge_list,
+            incoming_offsets,
+            incoming: incoming_list,
+        }
+    }
+}
+
+impl<N, E, I> FrozenGraph<N, E, I> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    #[inline]
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    #[inline]
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    #[inline]
+    pub fn edges(&self, src: usize) -> &[(usize, E)] {
+        &self.edges[self.offsets[src]..self.offsets[src + 1]]
+    }
+
+    #[inline]
+    pub fn incoming(&self, dst: usize) -> &[usize] {
+        &self.incoming[self.incoming_offsets[dst]..self.incoming_offsets[dst + 1]]
+    }
+
+    #[inline]
+    pub fn edge(&self, src: usize, dst: usize) -> Option<&E> {
+        self.edges(src)
+            .iter()
+            .find(|(d, _)| *d == dst)
+            .map(|(_, e)| e)
+    }
+
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn roots(&self) -> impl Iterator<Item = (usize, &N)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.incoming(*i).is_empty())
+    }
+}
+
+impl<N, E, I> FrozenGraph<N, E, I>
+where
+    I: NodeIndex<N>,
+{
+    #[inline]
+    pub fn node_index(&self, node: &N) -> Option<usize> {
+        self.index.find(&self.nodes, node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{IndexedGraph, SpillGraph};
+
+    #[test]
+    fn freeze_keeps_edges() {
+        let mut graph = SpillGraph::<char, u32, 1>::new();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        let c = graph.add_node('c');
+        graph.connect(a, b, 1);
+        graph.connect(a, c, 2);
+        graph.connect(b, c, 3);
+
+        let frozen = graph.freeze();
+        assert_eq!(frozen.len(), 3);
+        assert_eq!(frozen.edges(a), &[(b, 1), (c, 2)]);
+        assert_eq!(frozen.edges(b), &[(c, 3)]);
+        assert_eq!(frozen.edges(c), &[]);
+        assert_eq!(frozen.incoming(c), &[a, b]);
+        assert_eq!(frozen.edge(b, c), Some(&3));
+        assert_eq!(frozen.edge(c, b), None);
+        assert_eq!(frozen.edge_count(), 3);
+        assert_eq!(frozen.node_index(&'c'), Some(c));
+        assert_eq!(frozen.roots().map(|(i, _)| i).collect::<Vec<_>>(), vec![a]);
+    }
+
+    #[test]
+    fn freeze_keeps_hash_index() {
+        let mut graph = IndexedGraph::<u32, (), 2>::new();
+        for i in 0..100 {
+            let node = graph.add_node(i * 7);
+            if i > 0 {
+                graph.connect(node - 1, node, ());
+            }
+        }
+
+        let frozen = graph.freeze();
+        assert_eq!(frozen.node_index(&70), Some(10));
+        assert_eq!(frozen.node_index(&71), None);
+        assert_eq!(frozen.edges(10), &[(11, ())]);
+    }
+}
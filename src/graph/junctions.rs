@@ -0,0 +1,89 @@
+use super::{EdgeList, Graph, NodeIndex};
+use crate::grid::{Grid, GridCoordinate};
+use crate::point::CardinalNeighborsWrapping;
+use num::traits::{WrappingAdd, WrappingSub};
+use num::One;
+
+impl<T, const CAP: usize, L, I> Graph<(T, T), u32, CAP, L, I>
+where
+    (T, T): GridCoordinate + Eq,
+    T: Copy + WrappingAdd<Output = T> + WrappingSub<Output = T> + One,
+    L: EdgeList<u32>,
+    I: NodeIndex<(T, T)>,
+{
+    /// Build a graph of the junctions in a maze, where the edges are the number of steps
+    /// between them. Dead ends are kept, and so is any position `keep` returns true for.
+    pub fn from_grid_junctions<S, V, P, K>(grid: &Grid<(T, T), S, V>, passable: P, keep: K) -> Self
+    where
+        S: AsRef<[V]>,
+        P: Fn(&V) -> bool,
+        K: Fn(&(T, T)) -> bool,
+    {
+        let size = *grid.size();
+        let mut indices = vec![usize::MAX; size.area()];
+        let mut graph = Self::with_capacity(size.area() / 4);
+        for (pos, cell) in grid.iter() {
+            if passable(cell) {
+                indices[pos.index(&size)] = graph.add_node(pos);
+            }
+        }
+
+        for (pos, cell) in grid.iter() {
+            if !passable(cell) {
+                continue;
+            }
+
+            let src = indices[pos.index(&size)];
+            for next in pos.cardinal_neighbors_wrapping() {
+                if grid.cell(&next).is_some_and(&passable) {
+                    graph.connect(src, indices[next.index(&size)], 1);
+                }
+            }
+        }
+
+        graph.contract(|_, pos| keep(pos), |a, b| a + b);
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::SpillGraph;
+    use crate::grid::Grid;
+
+    const MAZE: &[u8] = b"\
+#########\
+#S..#...#\
+#.#.#.#.#\
+#.......#\
+###.###E#\
+#########";
+
+    #[test]
+    fn builds_junction_graph() {
+        let grid = Grid::with_storage((9u8, 6u8), MAZE);
+        let graph = SpillGraph::<(u8, u8), u32, 4>::from_grid_junctions(
+            &grid,
+            |c| *c != b'#',
+            |pos| matches!(grid.cell(pos), Some(b'S' | b'E')),
+        );
+
+        let index = |pos| graph.node_index(&pos).unwrap();
+        assert_eq!(
+            graph.nodes(),
+            &[(1, 1), (3, 3), (5, 3), (7, 3), (3, 4), (7, 4)]
+        );
+
+        let mut from_start = graph.edges(index((1, 1))).to_vec();
+        from_start.sort_unstable();
+        assert_eq!(from_start, vec![(index((3, 3)), 4), (index((3, 3)), 4)]);
+
+        let mut from_right = graph.edges(index((7, 3))).to_vec();
+        from_right.sort_unstable();
+        assert_eq!(
+            from_right,
+            vec![(index((5, 3)), 2), (index((5, 3)), 6), (index((7, 4)), 1)]
+        );
+        assert_eq!(graph.edges(index((3, 4))), &[(index((3, 3)), 1)]);
+    }
+}
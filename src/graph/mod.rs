@@ -0,0 +1,377 @@
+mod algorithms;
+mod frozen;
+mod junctions;
+mod mutation;
+mod storage;
+mod text;
+
+use arrayvec::ArrayVec;
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+pub use frozen::FrozenGraph;
+pub use storage::{EdgeList, LinearNodeIndex, NodeIndex, SpillVec};
+
+/// A graph with edges stored per node. `L` decides how a node's outgoing edges are stored,
+/// and `I` how nodes are looked up. The default is `CAP` edges inline per node and a linear
+/// scan for lookups, which is the fastest for small graphs.
+pub struct Graph<N, E, const CAP: usize, L = ArrayVec<(usize, E), CAP>, I = LinearNodeIndex> {
+    nodes: Vec<N>,
+    edges: Vec<L>,
+    incoming: Vec<Vec<usize>>,
+    index: I,
+    spooky_ghost: PhantomData<E>,
+}
+
+/// Keeps `CAP` edges inline, but won't panic if a node gets more.
+pub type SpillGraph<N, E, const CAP: usize> = Graph<N, E, CAP, SpillVec<(usize, E), CAP>>;
+
+/// A spilling graph with a hash index for the nodes, for when there are lots of them.
+pub type IndexedGraph<N, E, const CAP: usize> =
+    Graph<N, E, CAP, SpillVec<(usize, E), CAP>, FxHashMap<N, usize>>;
+
+impl<N, E, const CAP: usize, L, I> Graph<N, E, CAP, L, I>
+where
+    N: Eq,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        Graph {
+            nodes: Vec::with_capacity(cap),
+            edges: Vec::with_capacity(cap),
+            incoming: Vec::with_capacity(cap),
+            index: I::default(),
+            spooky_ghost: PhantomData,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn ensure_node(&mut self, node: N) -> usize {
+        match self.index.find(&self.nodes, &node) {
+            Some(index) => index,
+            None => self.add_node(node),
+        }
+    }
+
+    pub fn add_node(&mut self, node: N) -> usize {
+        let index = self.nodes.len();
+        self.index.insert(&node, index);
+        self.nodes.push(node);
+        self.edges.push(L::default());
+        self.incoming.push(Vec::new());
+        index
+    }
+
+    #[inline]
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    /// Changing the node so it no longer equals its old self will confuse a hash index.
+    #[inline]
+    pub fn node_mut(&mut self, index: usize) -> &mut N {
+        &mut self.nodes[index]
+    }
+
+    #[inline]
+    pub fn node_index(&self, node: &N) -> Option<usize> {
+        self.index.find(&self.nodes, node)
+    }
+
+    /// This one always scans, regardless of the index.
+    #[inline]
+    pub fn node_index_by_ref<K>(&self, key: &K) -> Option<usize>
+    where
+        K: Ord + ?Sized,
+        N: AsRef<K>,
+    {
+        self.nodes.iter().position(|n| n.as_ref() == key)
+    }
+
+    #[inline]
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn roots(&self) -> impl Iterator<Item = (usize, &N)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.incoming[*i].is_empty())
+    }
+
+    #[inline]
+    pub fn connect(&mut self, src: usize, dst: usize, edge: E) {
+        self.edges[src].push(dst, edge);
+        self.incoming[dst].push(src);
+    }
+
+    #[inline]
+    pub fn edges(&self, src: usize) -> &[(usize, E)] {
+        self.edges[src].as_slice()
+    }
+
+    /// The sources of all edges going into `dst`, in the order they were connected.
+    #[inline]
+    pub fn incoming(&self, dst: usize) -> &[usize] {
+        &self.incoming[dst]
+    }
+
+    #[inline]
+    pub fn edge(&self, src: usize, dst: usize) -> Option<&E> {
+        self.edges[src]
+            .as_slice()
+            .iter()
+            .find(|(d, _)| *d == dst)
+            .map(|(_, e)| e)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(|l| l.as_slice().len()).sum()
+    }
+}
+
+impl<N, E, const CAP: usize, L, I> Default for Graph<N, E, CAP, L, I>
+where
+    N: Eq,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, E, const CAP: usize, L, I> Graph<N, E, CAP, L, I>
+where
+    N: Eq,
+    E: Clone,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    /// Pack the edges into one list, for graphs that are done growing.
+    pub fn freeze(self) -> FrozenGraph<N, E, I> {
+        FrozenGraph::new(
+            self.nodes,
+            self.index,
+            self.edges.iter().map(|l| l.as_slice()),
+            self.incoming.iter().map(|l| l.as_slice()),
+        )
+    }
+}
+
+impl<N, E, const CAP: usize, L, I> Graph<N, E, CAP, L, I>
+where
+    N: Eq,
+    E: Copy,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    pub fn connect_mutual(&mut self, src: usize, dst: usize, edge: E) {
+        self.connect(src, dst, edge);
+        self.connect(dst, src, edge);
+    }
+}
+
+impl<N, E, const CAP: usize, L, I> Graph<N, E, CAP, L, I>
+where
+    N: Clone + Eq + Hash,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    /// The builder keeps its own map of nodes, so it's quick whatever the graph's index is.
+    pub fn builder() -> GraphBuilder<N, E, CAP, L, I> {
+        GraphBuilder {
+            graph: Self::new(),
+            map: FxHashMap::with_capacity_and_hasher(CAP * 8, Default::default()),
+        }
+    }
+}
+
+pub struct GraphBuilder<N, E, const CAP: usize, L = ArrayVec<(usize, E), CAP>, I = LinearNodeIndex>
+{
+    graph: Graph<N, E, CAP, L, I>,
+    map: FxHashMap<N, usize>,
+}
+
+impl<N, E, const CAP: usize, L, I> GraphBuilder<N, E, CAP, L, I>
+where
+    N: Clone + Eq + Hash,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    pub fn ensure(&mut self, node: N) -> usize {
+        match self.map.entry(node) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let index = self.graph.add_node(entry.key().clone());
+                *entry.insert(index)
+            }
+        }
+    }
+
+    pub fn ensure_connect(&mut self, src: N, dst: N, edge: E) {
+        let src = self.ensure(src);
+        let dst = self.ensure(dst);
+        self.graph.connect(src, dst, edge);
+    }
+
+    /// Look at the graph being built, e.g. to export it.
+    pub fn graph(&self) -> &Graph<N, E, CAP, L, I> {
+        &self.graph
+    }
+
+    pub fn to_graph(self) -> Graph<N, E, CAP, L, I> {
+        self.graph
+    }
+}
+
+impl<N, E, const CAP: usize, L, I> GraphBuilder<N, E, CAP, L, I>
+where
+    N: Clone + Eq + Hash,
+    E: Copy,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    pub fn ensure_connect_mutual(&mut self, src: N, dst: N, edge: E) {
+        let src = self.ensure(src);
+        let dst = self.ensure(dst);
+        self.graph.connect_mutual(src, dst, edge);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_graph() {
+        let mut graph = Graph::<String, u8, 16>::new();
+        assert_eq!(graph.ensure_node(String::from("hello")), 0);
+        assert_eq!(graph.ensure_node(String::from("world")), 1);
+        assert_eq!(graph.ensure_node(String::from("world")), 1);
+        assert_eq!(graph.ensure_node(String::from("blurg")), 2);
+
+        assert_eq!(graph.node_index_by_ref("blurg"), Some(2));
+        assert_eq!(graph.node_index_by_ref("hello"), Some(0));
+        assert_eq!(graph.node_index_by_ref("world"), Some(1));
+        assert_eq!(graph.node_index_by_ref("welt"), None);
+    }
+
+    #[test]
+    fn test_u32_graph() {
+        let mut graph = Graph::<u32, u8, 16>::new();
+        assert_eq!(graph.ensure_node(16), 0);
+        assert_eq!(graph.ensure_node(32), 1);
+        assert_eq!(graph.ensure_node(24), 2);
+        assert_eq!(graph.ensure_node(32), 1);
+
+        assert_eq!(graph.node_index(&16), Some(0));
+        assert_eq!(graph.node_index(&24), Some(2));
+        assert_eq!(graph.node_index(&32), Some(1));
+        assert_eq!(graph.node_index(&33), None);
+    }
+
+    #[test]
+    fn test_struct_graph() {
+        #[derive(Debug, Eq, PartialEq)]
+        struct CustomNode(u32, String);
+
+        impl AsRef<u32> for CustomNode {
+            fn as_ref(&self) -> &u32 {
+                &self.0
+            }
+        }
+
+        let mut graph = Graph::<CustomNode, u8, 16>::new();
+        assert_eq!(graph.add_node(CustomNode(16, String::from("Hello"))), 0);
+        assert_eq!(graph.add_node(CustomNode(32, String::from("World"))), 1);
+
+        assert_eq!(graph.node_index_by_ref(&16), Some(0));
+        assert_eq!(graph.node_index_by_ref(&32), Some(1));
+        assert_eq!(graph.node_index_by_ref(&33), None);
+    }
+
+    #[test]
+    fn test_spill_graph() {
+        let mut graph = SpillGraph::<u32, (), 2>::new();
+        let hub = graph.add_node(0);
+        for i in 1..=5 {
+            let other = graph.add_node(i);
+            graph.connect(hub, other, ());
+        }
+
+        assert_eq!(graph.edges(hub).len(), 5);
+        assert_eq!(graph.edge_count(), 5);
+        assert_eq!(graph.incoming(3), &[hub]);
+        assert_eq!(graph.roots().map(|(i, _)| i).collect::<Vec<_>>(), vec![hub]);
+    }
+
+    #[test]
+    fn test_indexed_graph() {
+        let mut graph = IndexedGraph::<u32, u8, 4>::new();
+        for i in 0..1000 {
+            assert_eq!(graph.ensure_node(i * 3), i as usize);
+        }
+        for i in 0..1000 {
+            assert_eq!(graph.ensure_node(i * 3), i as usize);
+        }
+
+        assert_eq!(graph.len(), 1000);
+        assert_eq!(graph.node_index(&300), Some(100));
+        assert_eq!(graph.node_index(&301), None);
+    }
+
+    #[test]
+    fn test_builder_keeps_index() {
+        let mut builder = IndexedGraph::<u32, (), 4>::builder();
+        for i in 0..1000 {
+            builder.ensure_connect_mutual(i, (i + 1) % 1000, ());
+        }
+
+        let graph = builder.to_graph();
+        assert_eq!(graph.len(), 1000);
+        assert_eq!(graph.node_index(&300), Some(300));
+        assert_eq!(graph.edges(0), &[(1, ()), (999, ())]);
+
+        let mut builder = Graph::<&str, (), 4>::builder();
+        builder.ensure_connect("a", "b", ());
+        assert_eq!(builder.ensure("b"), 1);
+        assert_eq!(builder.graph().node_index(&"b"), Some(1));
+    }
+
+    #[test]
+    fn test_incoming_and_roots() {
+        let mut graph = Graph::<&str, u8, 4>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.connect(a, c, 1);
+        graph.connect(b, c, 2);
+        graph.connect(c, d, 3);
+
+        assert_eq!(graph.incoming(c), &[a, b]);
+        assert_eq!(graph.incoming(a), &[]);
+        assert_eq!(
+            graph.roots().map(|(i, _)| i).collect::<Vec<_>>(),
+            vec![a, b]
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use super::{EdgeList, Graph, NodeIndex};
+use arrayvec::ArrayVec;
+use bit_vec::BitVec;
+
+impl<N, E, const CAP: usize, L, I> Graph<N, E, CAP, L, I>
+where
+    N: Eq,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    /// Remove the first edge from `src` to `dst`, returning it if there was one.
+    pub fn disconnect(&mut self, src: usize, dst: usize) -> Option<E> {
+        let position = self.edges[src]
+            .as_slice()
+            .iter()
+            .position(|(d, _)| *d == dst)?;
+        let (_, edge) = self.edges[src].remove(position);
+        if let Some(position) = self.incoming[dst].iter().position(|s| *s == src) {
+            self.incoming[dst].remove(position);
+        }
+
+        Some(edge)
+    }
+
+    /// Remove the edges in both directions, returning how many were removed.
+    pub fn disconnect_mutual(&mut self, a: usize, b: usize) -> usize {
+        self.disconnect(a, b).is_some() as usize + self.disconnect(b, a).is_some() as usize
+    }
+
+    /// Remove every edge going into or out of the node. Indices stay the same.
+    pub fn isolate(&mut self, index: usize) {
+        for (dst, _) in self.edges[index].as_slice().iter() {
+            self.incoming[*dst].retain(|s| *s != index);
+        }
+        self.edges[index].clear();
+
+        let incoming = std::mem::take(&mut self.incoming[index]);
+        for src in incoming {
+            self.edges[src].retain(|(d, _)| *d != index);
+        }
+    }
+
+    /// Remove the node and its edges. Every index above it moves down one.
+    pub fn remove_node(&mut self, index: usize) -> N {
+        self.isolate(index);
+
+        let node = self.nodes.remove(index);
+        self.edges.remove(index);
+        self.incoming.remove(index);
+        for list in self.edges.iter_mut() {
+            for (dst, _) in list.as_mut_slice().iter_mut() {
+                if *dst > index {
+                    *dst -= 1;
+                }
+            }
+        }
+        for list in self.incoming.iter_mut() {
+            for src in list.iter_mut() {
+                if *src > index {
+                    *src -= 1;
+                }
+            }
+        }
+        self.index.rebuild(&self.nodes);
+
+        node
+    }
+
+    /// Remove all nodes that fail the predicate in one pass, keeping the order of the rest. It
+    /// returns where each old index went, with `usize::MAX` for the removed ones.
+    pub fn retain_nodes<F>(&mut self, mut f: F) -> Vec<usize>
+    where
+        F: FnMut(usize, &N) -> bool,
+    {
+        let mut mapping = vec![usize::MAX; self.nodes.len()];
+        let mut next = 0;
+        for (i, node) in self.nodes.iter().enumerate() {
+            if f(i, node) {
+                mapping[i] = next;
+                next += 1;
+            }
+        }
+        if next == self.nodes.len() {
+            return mapping;
+        }
+
+        let mut i = 0;
+        self.nodes.retain(|_| {
+            i += 1;
+            mapping[i - 1] != usize::MAX
+        });
+        let mut i = 0;
+        self.edges.retain(|_| {
+            i += 1;
+            mapping[i - 1] != usize::MAX
+        });
+        let mut i = 0;
+        self.incoming.retain(|_| {
+            i += 1;
+            mapping[i - 1] != usize::MAX
+        });
+
+        for list in self.edges.iter_mut() {
+            list.retain(|(dst, _)| mapping[*dst] != usize::MAX);
+            for (dst, _) in list.as_mut_slice().iter_mut() {
+                *dst = mapping[*dst];
+            }
+        }
+        for list in self.incoming.iter_mut() {
+            list.retain(|src| mapping[*src] != usize::MAX);
+            for src in list.iter_mut() {
+                *src = mapping[*src];
+            }
+        }
+        self.index.rebuild(&self.nodes);
+
+        mapping
+    }
+
+    /// Remove every node that only links two other nodes, connecting those two directly
+    /// with the edges combined. A node is only removed if it has exactly two neighbours,
+    /// at most one edge each way to each of them, and `keep` returns false for it. The
+    /// new edges are added even if there already is one, so two corridors between the
+    /// same junctions stay separate. Returns the number of nodes removed.
+    pub fn contract<K, F>(&mut self, keep: K, combine: F) -> usize
+    where
+        K: Fn(usize, &N) -> bool,
+        F: Fn(&E, &E) -> E,
+    {
+        let len = self.nodes.len();
+        let mut removed = BitVec::from_elem(len, false);
+        let mut bypasses = Vec::with_capacity(4);
+
+        for v in 0..len {
+            if keep(v, &self.nodes[v]) {
+                continue;
+            }
+
+            let mut neighbors = ArrayVec::<usize, 2>::new();
+            let mut passes_through = true;
+            let sources = self.incoming[v].as_slice();
+            let targets = self.edges[v].as_slice();
+            for (i, n) in sources.iter().enumerate() {
+                if *n == v || sources[..i].contains(n) {
+                    passes_through = false;
+                }
+            }
+            for (i, (n, _)) in targets.iter().enumerate() {
+                if *n == v || targets[..i].iter().any(|(d, _)| d == n) {
+                    passes_through = false;
+                }
+            }
+            for n in sources.iter().chain(targets.iter().map(|(d, _)| d)) {
+                if !neighbors.contains(n) && neighbors.try_push(*n).is_err() {
+                    passes_through = false;
+                }
+            }
+            if !passes_through || neighbors.len() != 2 {
+                continue;
+            }
+
+            bypasses.clear();
+            for src in neighbors.iter().copied() {
+                for (_, edge_in) in self.edges[src].as_slice().iter().filter(|(d, _)| *d == v) {
+                    for (dst, edge_out) in self.edges[v].as_slice().iter() {
+                        if *dst != src {
+                            bypasses.push((src, *dst, combine(edge_in, edge_out)));
+                        }
+                    }
+                }
+            }
+
+            self.isolate(v);
+            for (src, dst, edge) in bypasses.drain(..) {
+                self.connect(src, dst, edge);
+            }
+            removed.set(v, true);
+        }
+
+        let count = removed.count_ones() as usize;
+        self.retain_nodes(|i, _| !removed[i]);
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, IndexedGraph, SpillGraph};
+
+    #[test]
+    fn disconnect_updates_incoming() {
+        let mut graph = Graph::<u32, u32, 4>::new();
+        let a = graph.add_node(1);
+        let b = graph.add_node(2);
+        graph.connect_mutual(a, b, 7);
+
+        assert_eq!(graph.disconnect(a, b), Some(7));
+        assert_eq!(graph.disconnect(a, b), None);
+        assert_eq!(graph.edges(a), &[]);
+        assert_eq!(graph.incoming(b), &[]);
+        assert_eq!(graph.incoming(a), &[b]);
+        assert_eq!(graph.disconnect_mutual(a, b), 1);
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn remove_node_compacts_indices() {
+        let mut graph = IndexedGraph::<char, u32, 4>::new();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        let c = graph.add_node('c');
+        let d = graph.add_node('d');
+        graph.connect(a, b, 1);
+        graph.connect(b, c, 2);
+        graph.connect(a, d, 3);
+        graph.connect(d, c, 4);
+
+        assert_eq!(graph.remove_node(b), 'b');
+        assert_eq!(graph.nodes(), &['a', 'c', 'd']);
+        assert_eq!(graph.edges(0), &[(2, 3)]);
+        assert_eq!(graph.edges(2), &[(1, 4)]);
+        assert_eq!(graph.incoming(1), &[2]);
+        assert_eq!(graph.node_index(&'d'), Some(2));
+        assert_eq!(graph.node_index(&'b'), None);
+    }
+
+    #[test]
+    fn retain_nodes_returns_mapping() {
+        let mut graph = Graph::<u32, (), 4>::new();
+        for i in 0..5 {
+            graph.add_node(i);
+        }
+        for i in 0..4 {
+            graph.connect(i, i + 1, ());
+        }
+
+        let mapping = graph.retain_nodes(|_, n| n % 2 == 0);
+        assert_eq!(mapping, vec![0, usize::MAX, 1, usize::MAX, 2]);
+        assert_eq!(graph.nodes(), &[0, 2, 4]);
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn contract_merges_chains() {
+        // a - b - c - d, with e hanging off c.
+        let mut graph = SpillGraph::<char, u32, 2>::new();
+        let nodes: Vec<usize> = "abcde".chars().map(|c| graph.add_node(c)).collect();
+        graph.connect_mutual(nodes[0], nodes[1], 1);
+        graph.connect_mutual(nodes[1], nodes[2], 2);
+        graph.connect_mutual(nodes[2], nodes[3], 4);
+        graph.connect_mutual(nodes[2], nodes[4], 8);
+
+        assert_eq!(graph.contract(|_, _| false, |a, b| a + b), 1);
+        assert_eq!(graph.nodes(), &['a', 'c', 'd', 'e']);
+        assert_eq!(graph.edge(0, 1), Some(&3));
+        assert_eq!(graph.edge(1, 0), Some(&3));
+        assert_eq!(graph.edge(1, 2), Some(&4));
+        assert_eq!(graph.edge_count(), 6);
+    }
+
+    #[test]
+    fn contract_respects_keep_and_direction() {
+        let mut graph = Graph::<u32, u32, 4>::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.connect(0, 1, 1);
+        graph.connect(1, 2, 10);
+        graph.connect(2, 3, 100);
+
+        assert_eq!(graph.contract(|_, n| *n == 2, |a, b| a + b), 1);
+        assert_eq!(graph.nodes(), &[0, 2, 3]);
+        assert_eq!(graph.edges(0), &[(1, 11)]);
+        assert_eq!(graph.edges(1), &[(2, 100)]);
+    }
+}
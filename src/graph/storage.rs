@@ -0,0 +1,269 @@
+use arrayvec::ArrayVec;
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+/// The outgoing edges of a single node.
+pub trait EdgeList<E>: Default {
+    fn push(&mut self, dst: usize, edge: E);
+    fn as_slice(&self) -> &[(usize, E)];
+    fn as_mut_slice(&mut self) -> &mut [(usize, E)];
+    /// Remove the edge at `index`, keeping the order of the rest.
+    fn remove(&mut self, index: usize) -> (usize, E);
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&(usize, E)) -> bool;
+    fn clear(&mut self);
+}
+
+/// Fixed capacity, stored inline. Panics when more than `CAP` edges are added.
+impl<E, const CAP: usize> EdgeList<E> for ArrayVec<(usize, E), CAP> {
+    #[inline]
+    fn push(&mut self, dst: usize, edge: E) {
+        ArrayVec::push(self, (dst, edge))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[(usize, E)] {
+        ArrayVec::as_slice(self)
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [(usize, E)] {
+        ArrayVec::as_mut_slice(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, index: usize) -> (usize, E) {
+        ArrayVec::remove(self, index)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&(usize, E)) -> bool,
+    {
+        ArrayVec::retain(self, |e| f(e))
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        ArrayVec::clear(self)
+    }
+}
+
+impl<E> EdgeList<E> for Vec<(usize, E)> {
+    #[inline]
+    fn push(&mut self, dst: usize, edge: E) {
+        Vec::push(self, (dst, edge))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[(usize, E)] {
+        Vec::as_slice(self)
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [(usize, E)] {
+        Vec::as_mut_slice(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, index: usize) -> (usize, E) {
+        Vec::remove(self, index)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&(usize, E)) -> bool,
+    {
+        Vec::retain(self, f)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+}
+
+/// Inline storage for up to `CAP` elements, moving to the heap once it's full.
+#[derive(Debug, Clone)]
+pub enum SpillVec<T, const CAP: usize> {
+    Inline(ArrayVec<T, CAP>),
+    Heap(Vec<T>),
+}
+
+impl<T, const CAP: usize> SpillVec<T, CAP> {
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        match self {
+            SpillVec::Inline(list) => {
+                if let Err(err) = list.try_push(value) {
+                    let mut heap = Vec::with_capacity((CAP * 2).max(4));
+                    heap.extend(list.drain(..));
+                    heap.push(err.element());
+                    *self = SpillVec::Heap(heap);
+                }
+            }
+            SpillVec::Heap(list) => list.push(value),
+        }
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            SpillVec::Inline(list) => list.as_slice(),
+            SpillVec::Heap(list) => list.as_slice(),
+        }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match self {
+            SpillVec::Inline(list) => list.as_mut_slice(),
+            SpillVec::Heap(list) => list.as_mut_slice(),
+        }
+    }
+
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> T {
+        match self {
+            SpillVec::Inline(list) => list.remove(index),
+            SpillVec::Heap(list) => list.remove(index),
+        }
+    }
+
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        match self {
+            SpillVec::Inline(list) => list.retain(|v| f(v)),
+            SpillVec::Heap(list) => list.retain(f),
+        }
+    }
+
+    /// Clearing keeps the heap allocation if it has spilled.
+    #[inline]
+    pub fn clear(&mut self) {
+        match self {
+            SpillVec::Inline(list) => list.clear(),
+            SpillVec::Heap(list) => list.clear(),
+        }
+    }
+
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, SpillVec::Heap(_))
+    }
+}
+
+impl<T, const CAP: usize> Default for SpillVec<T, CAP> {
+    fn default() -> Self {
+        SpillVec::Inline(ArrayVec::new())
+    }
+}
+
+impl<E, const CAP: usize> EdgeList<E> for SpillVec<(usize, E), CAP> {
+    #[inline]
+    fn push(&mut self, dst: usize, edge: E) {
+        SpillVec::push(self, (dst, edge))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[(usize, E)] {
+        SpillVec::as_slice(self)
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [(usize, E)] {
+        SpillVec::as_mut_slice(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, index: usize) -> (usize, E) {
+        SpillVec::remove(self, index)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&(usize, E)) -> bool,
+    {
+        SpillVec::retain(self, f)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        SpillVec::clear(self)
+    }
+}
+
+/// How the graph finds the index of a node it already has.
+pub trait NodeIndex<N>: Default {
+    fn find(&self, nodes: &[N], node: &N) -> Option<usize>;
+    fn insert(&mut self, node: &N, index: usize);
+    /// Called when node indices have shifted.
+    fn rebuild(&mut self, nodes: &[N]);
+}
+
+/// Scan through the nodes. It needs nothing more than `Eq`, which is good enough
+/// for small graphs.
+#[derive(Default)]
+pub struct LinearNodeIndex;
+
+impl<N> NodeIndex<N> for LinearNodeIndex
+where
+    N: Eq,
+{
+    #[inline]
+    fn find(&self, nodes: &[N], node: &N) -> Option<usize> {
+        nodes.iter().position(|n| n == node)
+    }
+
+    #[inline]
+    fn insert(&mut self, _: &N, _: usize) {}
+
+    #[inline]
+    fn rebuild(&mut self, _: &[N]) {}
+}
+
+impl<N> NodeIndex<N> for FxHashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+{
+    #[inline]
+    fn find(&self, _: &[N], node: &N) -> Option<usize> {
+        self.get(node).copied()
+    }
+
+    #[inline]
+    fn insert(&mut self, node: &N, index: usize) {
+        self.entry(node.clone()).or_insert(index);
+    }
+
+    fn rebuild(&mut self, nodes: &[N]) {
+        self.clear();
+        for (index, node) in nodes.iter().enumerate() {
+            NodeIndex::insert(self, node, index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spill_vec_moves_to_heap() {
+        let mut list = SpillVec::<u32, 2>::default();
+        list.push(1);
+        list.push(2);
+        assert!(!list.is_spilled());
+        list.push(3);
+        list.push(4);
+        assert!(list.is_spilled());
+        assert_eq!(list.as_slice(), &[1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,244 @@
+use super::{EdgeList, Graph, NodeIndex};
+use std::fmt::Write;
+
+impl<N, E, const CAP: usize, L, I> Graph<N, E, CAP, L, I>
+where
+    N: Eq,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    /// Render the graph in Graphviz's DOT format. An empty edge label leaves it out.
+    pub fn to_dot<FN, FE>(&self, node_label: FN, edge_label: FE) -> String
+    where
+        FN: Fn(usize, &N) -> String,
+        FE: Fn(&E) -> String,
+    {
+        let mut res = String::with_capacity(32 + self.len() * 24);
+        res.push_str("digraph {\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            writeln!(
+                res,
+                "  n{} [label=\"{}\"];",
+                i,
+                dot_escape(&node_label(i, node))
+            )
+            .unwrap();
+        }
+        for (src, edges) in self.edges.iter().enumerate() {
+            for (dst, edge) in edges.as_slice().iter() {
+                let label = edge_label(edge);
+                if label.is_empty() {
+                    writeln!(res, "  n{} -> n{};", src, dst).unwrap();
+                } else {
+                    writeln!(
+                        res,
+                        "  n{} -> n{} [label=\"{}\"];",
+                        src,
+                        dst,
+                        dot_escape(&label)
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        res.push_str("}\n");
+
+        res
+    }
+
+    /// One edge per line as `src dst label`, leaving out the label if it's empty.
+    pub fn to_edge_list<FN, FE>(&self, node_label: FN, edge_label: FE) -> String
+    where
+        FN: Fn(&N) -> String,
+        FE: Fn(&E) -> String,
+    {
+        let mut res = String::with_capacity(self.edge_count() * 8);
+        for (src, edges) in self.edges.iter().enumerate() {
+            for (dst, edge) in edges.as_slice().iter() {
+                let label = edge_label(edge);
+                res.push_str(&node_label(&self.nodes[src]));
+                res.push(' ');
+                res.push_str(&node_label(&self.nodes[*dst]));
+                if !label.is_empty() {
+                    res.push(' ');
+                    res.push_str(&label);
+                }
+                res.push('\n');
+            }
+        }
+
+        res
+    }
+
+    /// One node per line as `node: dst1 dst2 ...`, the way AoC likes to give them.
+    pub fn to_adjacency_list<FN>(&self, node_label: FN) -> String
+    where
+        FN: Fn(&N) -> String,
+    {
+        let mut res = String::with_capacity(self.len() * 16);
+        for (src, edges) in self.edges.iter().enumerate() {
+            res.push_str(&node_label(&self.nodes[src]));
+            res.push(':');
+            for (dst, _) in edges.as_slice().iter() {
+                res.push(' ');
+                res.push_str(&node_label(&self.nodes[*dst]));
+            }
+            res.push('\n');
+        }
+
+        res
+    }
+
+    /// Read lines of `src dst [edge]` separated by whitespace. The edge callback gets
+    /// `None` if the third column is missing.
+    pub fn from_edge_list<'i, FN, FE>(input: &'i [u8], node: FN, edge: FE) -> Self
+    where
+        FN: Fn(&'i [u8]) -> N,
+        FE: Fn(Option<&'i [u8]>) -> E,
+    {
+        let mut graph = Self::new();
+        for line in input.split(|b| *b == b'\n') {
+            let mut tokens = line
+                .split(|b| b.is_ascii_whitespace())
+                .filter(|t| !t.is_empty());
+            let (Some(src), Some(dst)) = (tokens.next(), tokens.next()) else {
+                continue;
+            };
+
+            let src = graph.ensure_node(node(src));
+            let dst = graph.ensure_node(node(dst));
+            graph.connect(src, dst, edge(tokens.next()));
+        }
+
+        graph
+    }
+}
+
+impl<N, E, const CAP: usize, L, I> Graph<N, E, CAP, L, I>
+where
+    N: Eq,
+    E: Copy,
+    L: EdgeList<E>,
+    I: NodeIndex<N>,
+{
+    /// Read lines of `node: dst1 dst2 ...`. If `mutual` is set, each listed edge goes
+    /// both ways, which is usually how AoC means it.
+    pub fn from_adjacency_list<'i, FN>(input: &'i [u8], node: FN, edge: E, mutual: bool) -> Self
+    where
+        FN: Fn(&'i [u8]) -> N,
+    {
+        let mut graph = Self::new();
+        for line in input.split(|b| *b == b'\n') {
+            let mut tokens = line
+                .split(|b| b.is_ascii_whitespace())
+                .filter(|t| !t.is_empty());
+            let Some(src) = tokens.next() else {
+                continue;
+            };
+
+            let src = graph.ensure_node(node(src.strip_suffix(b":").unwrap_or(src)));
+            for dst in tokens {
+                let dst = graph.ensure_node(node(dst));
+                if mutual {
+                    graph.connect_mutual(src, dst, edge);
+                } else {
+                    graph.connect(src, dst, edge);
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            _ => res.push(c),
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, IndexedGraph};
+
+    fn label(n: &&[u8]) -> String {
+        String::from_utf8_lossy(n).into_owned()
+    }
+
+    #[test]
+    fn edge_list_round_trip() {
+        let input = b"a b 3\nb c 4\n\na c 10\n";
+        let graph = Graph::<&[u8], u32, 4>::from_edge_list(
+            input,
+            |n| n,
+            |e| std::str::from_utf8(e.unwrap()).unwrap().parse().unwrap(),
+        );
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.edge(0, 2), Some(&10));
+        let text = graph.to_edge_list(label, |e| e.to_string());
+        assert_eq!(text.as_bytes(), b"a b 3\na c 10\nb c 4\n");
+
+        let parsed = Graph::<&[u8], u32, 4>::from_edge_list(
+            text.as_bytes(),
+            |n| n,
+            |e| std::str::from_utf8(e.unwrap()).unwrap().parse().unwrap(),
+        );
+        assert_eq!(parsed.nodes(), graph.nodes());
+        assert_eq!(parsed.edge(0, 2), Some(&10));
+        assert_eq!(parsed.to_edge_list(label, |e| e.to_string()), text);
+    }
+
+    #[test]
+    fn adjacency_list_round_trip() {
+        let input = b"jqt: rhn xhk\nrhn: xhk bvb\n";
+        let graph = IndexedGraph::<&[u8], (), 4>::from_adjacency_list(input, |n| n, (), true);
+
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.edge_count(), 8);
+        let text = graph.to_adjacency_list(label);
+        assert_eq!(
+            text,
+            "jqt: rhn xhk\nrhn: jqt xhk bvb\nxhk: jqt rhn\nbvb: rhn\n"
+        );
+
+        // It already has both directions, so it goes back in without adding them again.
+        let parsed =
+            IndexedGraph::<&[u8], (), 4>::from_adjacency_list(text.as_bytes(), |n| n, (), false);
+        assert_eq!(parsed.nodes(), graph.nodes());
+        assert_eq!(parsed.edge_count(), 8);
+        assert_eq!(parsed.to_adjacency_list(label), text);
+    }
+
+    #[test]
+    fn dot_export() {
+        let mut graph = Graph::<&str, u32, 4>::new();
+        let a = graph.add_node("start");
+        let b = graph.add_node("say \"hi\"");
+        graph.connect(a, b, 5);
+        graph.connect(b, a, 0);
+
+        let dot = graph.to_dot(
+            |i, n| format!("{i}: {n}"),
+            |e| if *e > 0 { e.to_string() } else { String::new() },
+        );
+        assert_eq!(
+            dot,
+            "digraph {
+  n0 [label=\"0: start\"];
+  n1 [label=\"1: say \\\"hi\\\"\"];
+  n0 -> n1 [label=\"5\"];
+  n1 -> n0;
+}
+"
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use super::{Grid, GridCoordinate};
+use crate::utils::CardinalDirection;
+use num::{FromPrimitive, ToPrimitive};
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign};
+
+/// A 2D grid of booleans packed into `u64` words, with each row starting on a new word.
+/// Whole-grid operations work a word at a time, so cellular automata and BFS frontiers
+/// can step 64 cells at once.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BitGrid<C> {
+    words: Vec<u64>,
+    row_words: usize,
+    width: usize,
+    height: usize,
+    size: (C, C),
+}
+
+impl<C> BitGrid<C>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive + FromPrimitive,
+{
+    pub fn new(size: (C, C)) -> Self {
+        let width = size.0.to_usize().unwrap();
+        let height = size.1.to_usize().unwrap();
+        let row_words = width.div_ceil(64);
+
+        Self {
+            words: vec![0; row_words * height],
+            row_words,
+            width,
+            height,
+            size,
+        }
+    }
+
+    /// Set the cells of the grid that pass the predicate.
+    pub fn from_grid<S, T, F>(grid: &Grid<(C, C), S, T>, pred: F) -> Self
+    where
+        S: AsRef<[T]>,
+        F: Fn(&T) -> bool,
+    {
+        let mut bits = Self::new(*grid.size());
+        for (i, cell) in grid.as_slice()[..bits.width * bits.height]
+            .iter()
+            .enumerate()
+        {
+            if pred(cell) {
+                let (x, y) = (i % bits.width, i / bits.width);
+                bits.words[y * bits.row_words + x / 64] |= 1 << (x % 64);
+            }
+        }
+
+        bits
+    }
+
+    #[inline]
+    pub fn size(&self) -> &(C, C) {
+        &self.size
+    }
+
+    #[inline]
+    pub(crate) fn bit_of(&self, pos: &(C, C)) -> Option<(usize, u64)> {
+        let (x, y) = (pos.0.to_usize()?, pos.1.to_usize()?);
+        if x < self.width && y < self.height {
+            Some((y * self.row_words + x / 64, 1 << (x % 64)))
+        } else {
+            None
+        }
+    }
+
+    /// Out of bounds reads as false.
+    #[inline]
+    pub fn get(&self, pos: &(C, C)) -> bool {
+        match self.bit_of(pos) {
+            Some((index, mask)) => self.words[index] & mask != 0,
+            None => false,
+        }
+    }
+
+    /// Set the bit, returning what it was before. Out of bounds is ignored.
+    #[inline]
+    pub fn set(&mut self, pos: &(C, C), value: bool) -> bool {
+        if let Some((index, mask)) = self.bit_of(pos) {
+            let old = self.words[index] & mask != 0;
+            if value {
+                self.words[index] |= mask;
+            } else {
+                self.words[index] &= !mask;
+            }
+
+            old
+        } else {
+            false
+        }
+    }
+
+    /// Flip the bit, returning the new value.
+    #[inline]
+    pub fn toggle(&mut self, pos: &(C, C)) -> bool {
+        if let Some((index, mask)) = self.bit_of(pos) {
+            self.words[index] ^= mask;
+            self.words[index] & mask != 0
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Clear the bits that are set in `other`.
+    pub fn and_not(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= !*b;
+        }
+    }
+
+    /// Move every bit one step in the direction. Bits that fall off the edge are lost.
+    pub fn shift(&mut self, dir: CardinalDirection) {
+        if self.words.is_empty() {
+            return;
+        }
+
+        let rw = self.row_words;
+        match dir {
+            CardinalDirection::North => {
+                self.words.copy_within(rw.., 0);
+                let len = self.words.len();
+                self.words[len.saturating_sub(rw)..].fill(0);
+            }
+            CardinalDirection::South => {
+                let len = self.words.len();
+                self.words
+                    .copy_within(..len.saturating_sub(rw), rw.min(len));
+                self.words[..rw.min(len)].fill(0);
+            }
+            CardinalDirection::West => {
+                for row in self.words.chunks_exact_mut(rw.max(1)) {
+                    for i in 0..row.len() {
+                        let carry = row.get(i + 1).map(|w| w << 63).unwrap_or(0);
+                        row[i] = (row[i] >> 1) | carry;
+                    }
+                }
+            }
+            CardinalDirection::East => {
+                for row in self.words.chunks_exact_mut(rw.max(1)) {
+                    for i in (0..row.len()).rev() {
+                        let carry = if i > 0 { row[i - 1] >> 63 } else { 0 };
+                        row[i] = (row[i] << 1) | carry;
+                    }
+                }
+                self.mask_padding();
+            }
+        }
+    }
+
+    /// Copy of the grid with every bit moved one step in the direction.
+    pub fn shifted(&self, dir: CardinalDirection) -> Self
+    where
+        C: Clone,
+    {
+        let mut res = self.clone();
+        res.shift(dir);
+        res
+    }
+
+    /// Set every bit next to a set bit, like one step of a BFS frontier.
+    pub fn dilate(&mut self)
+    where
+        C: Clone,
+    {
+        let copy = self.clone();
+        for dir in CardinalDirection::NWES {
+            *self |= &copy.shifted(dir);
+        }
+    }
+
+    /// The positions of the set bits, row by row.
+    pub fn iter_ones(&self) -> impl Iterator<Item = (C, C)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, word)| {
+            let (y, base) = (i / self.row_words, (i % self.row_words) * 64);
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some((C::from_usize(base + bit)?, C::from_usize(y)?))
+            })
+        })
+    }
+
+    fn mask_padding(&mut self) {
+        let extra = self.width % 64;
+        if extra != 0 {
+            let mask = (1u64 << extra) - 1;
+            for row in self.words.chunks_exact_mut(self.row_words) {
+                row[self.row_words - 1] &= mask;
+            }
+        }
+    }
+}
+
+impl<C> BitAndAssign<&BitGrid<C>> for BitGrid<C> {
+    fn bitand_assign(&mut self, rhs: &BitGrid<C>) {
+        for (a, b) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *a &= *b;
+        }
+    }
+}
+
+impl<C> BitOrAssign<&BitGrid<C>> for BitGrid<C> {
+    fn bitor_assign(&mut self, rhs: &BitGrid<C>) {
+        for (a, b) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *a |= *b;
+        }
+    }
+}
+
+impl<C> BitXorAssign<&BitGrid<C>> for BitGrid<C> {
+    fn bitxor_assign(&mut self, rhs: &BitGrid<C>) {
+        for (a, b) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *a ^= *b;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(s: &str) -> BitGrid<usize> {
+        let grid: Grid<(usize, usize), Vec<bool>, bool> = s.parse().unwrap();
+        BitGrid::from_grid(&grid, |b| *b)
+    }
+
+    #[test]
+    fn get_set_toggle() {
+        let mut grid = BitGrid::<u8>::new((100, 3));
+        assert!(!grid.set(&(70, 1), true));
+        assert!(grid.set(&(70, 1), true));
+        assert!(grid.get(&(70, 1)));
+        assert!(!grid.get(&(200, 1)));
+        assert!(grid.toggle(&(3, 2)));
+        assert!(!grid.toggle(&(70, 1)));
+        assert_eq!(grid.count_ones(), 1);
+        assert_eq!(grid.iter_ones().collect::<Vec<_>>(), vec![(3, 2)]);
+    }
+
+    #[test]
+    fn shifts_across_words() {
+        let mut grid = BitGrid::<u16>::new((130, 2));
+        grid.set(&(63, 0), true);
+        grid.set(&(129, 1), true);
+
+        grid.shift(CardinalDirection::East);
+        assert_eq!(grid.iter_ones().collect::<Vec<_>>(), vec![(64, 0)]);
+        grid.shift(CardinalDirection::South);
+        assert_eq!(grid.iter_ones().collect::<Vec<_>>(), vec![(64, 1)]);
+        grid.shift(CardinalDirection::West);
+        grid.shift(CardinalDirection::North);
+        assert_eq!(grid.iter_ones().collect::<Vec<_>>(), vec![(63, 0)]);
+        grid.shift(CardinalDirection::North);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn shifts_empty_grids() {
+        for size in [(0, 0), (5, 0), (0, 5)] {
+            let mut grid = BitGrid::<u8>::new(size);
+            for dir in CardinalDirection::NWES {
+                grid.shift(dir);
+            }
+            assert_eq!(grid.count_ones(), 0);
+        }
+    }
+
+    #[test]
+    fn word_ops_and_dilate() {
+        let a = bits("##..\n....\n");
+        let b = bits(".##.\n...#\n");
+
+        let mut and = a.clone();
+        and &= &b;
+        assert_eq!(and, bits(".#..\n....\n"));
+
+        let mut xor = a.clone();
+        xor ^= &b;
+        assert_eq!(xor, bits("#.#.\n...#\n"));
+
+        let mut or = a.clone();
+        or |= &b;
+        or.and_not(&and);
+        assert_eq!(or, xor);
+
+        let mut frontier = bits("....\n.#..\n....\n");
+        frontier.dilate();
+        assert_eq!(frontier, bits(".#..\n###.\n.#..\n"));
+    }
+}
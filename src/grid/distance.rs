@@ -0,0 +1,211 @@
+use super::{Grid, GridCoordinate, CARDINAL_STENCIL};
+use crate::search::{bfs, dijkstra, Order};
+use bit_vec::BitVec;
+use num::{FromPrimitive, ToPrimitive};
+use rustc_hash::FxHashMap;
+
+/// Distance of the cells that can't be reached from any source.
+pub const UNREACHABLE: u32 = u32::MAX;
+
+impl<C, S, T> Grid<(C, C), S, T>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive + FromPrimitive,
+    S: AsRef<[T]>,
+{
+    /// The number of steps from the closest source to every cell, moving between
+    /// passable cardinal neighbours. Sources are always distance 0, passable or not.
+    pub fn distance_map<I, P>(&self, sources: I, passable: P) -> Grid<(C, C), Vec<u32>, u32>
+    where
+        I: IntoIterator<Item = (C, C)>,
+        P: Fn(&T) -> bool,
+    {
+        let (w, h) = self.dims_usize();
+        let data = self.storage.as_ref();
+        let mut distances = vec![UNREACHABLE; w * h];
+        let mut search = bfs().with_seen_space(BitVec::from_elem(w * h, false));
+        for source in sources {
+            if source.in_bounds(&self.size) {
+                search.push((source.index(&self.size), 0u32));
+            }
+        }
+
+        search.find(|search, (index, cost)| {
+            distances[index] = cost;
+            for next in neighbor_indices(index, w, h) {
+                if passable(&data[next]) {
+                    search.push((next, cost + 1));
+                }
+            }
+
+            None::<()>
+        });
+
+        self.with_distances(distances)
+    }
+
+    /// Like `distance_map`, but the cost of each step comes from the callback, which gets
+    /// the cell it's moving from and to. Returning `None` blocks the move.
+    pub fn distance_map_weighted<I, F>(&self, sources: I, cost: F) -> Grid<(C, C), Vec<u32>, u32>
+    where
+        I: IntoIterator<Item = (C, C)>,
+        F: Fn(&T, &T) -> Option<u32>,
+    {
+        let (w, h) = self.dims_usize();
+        let data = self.storage.as_ref();
+        let mut distances = vec![UNREACHABLE; w * h];
+        let mut search = dijkstra().with_seen_space(FxHashMap::<usize, u32>::default());
+        for source in sources {
+            if source.in_bounds(&self.size) {
+                search.push((source.index(&self.size), 0u32));
+            }
+        }
+
+        search.find(|search, (index, current)| {
+            if distances[index] <= current {
+                return None::<()>;
+            }
+
+            distances[index] = current;
+            for next in neighbor_indices(index, w, h) {
+                if let Some(step) = cost(&data[index], &data[next]) {
+                    search.push((next, current + step));
+                }
+            }
+
+            None
+        });
+
+        self.with_distances(distances)
+    }
+
+    #[inline]
+    fn dims_usize(&self) -> (usize, usize) {
+        (
+            self.size.0.to_usize().unwrap(),
+            self.size.1.to_usize().unwrap(),
+        )
+    }
+
+    fn with_distances(&self, distances: Vec<u32>) -> Grid<(C, C), Vec<u32>, u32> {
+        let (w, h) = self.dims_usize();
+        Grid::new_with_default(
+            (C::from_usize(w).unwrap(), C::from_usize(h).unwrap()),
+            distances,
+            UNREACHABLE,
+        )
+    }
+}
+
+impl<C, S> Grid<(C, C), S, u32>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive + FromPrimitive,
+    S: AsRef<[u32]>,
+{
+    /// Every ordered pair of reachable cells that are at most `radius` apart by Manhattan
+    /// distance, along with that distance. This is what day 20's cheats are.
+    pub fn pairs_within(&self, radius: usize) -> impl Iterator<Item = ((C, C), (C, C), u32)> + '_ {
+        let (w, h) = self.dims_usize();
+        let data = self.storage.as_ref();
+        let r = radius as isize;
+
+        (0..w * h)
+            .filter(move |i| data[*i] != UNREACHABLE)
+            .flat_map(move |a| {
+                let (ax, ay) = ((a % w) as isize, (a / w) as isize);
+                (-r..=r).flat_map(move |dy| {
+                    let rx = r - dy.abs();
+                    (-rx..=rx).filter_map(move |dx| {
+                        let (bx, by) = (ax + dx, ay + dy);
+                        if (dx, dy) == (0, 0)
+                            || bx < 0
+                            || by < 0
+                            || bx as usize >= w
+                            || by as usize >= h
+                            || data[by as usize * w + bx as usize] == UNREACHABLE
+                        {
+                            return None;
+                        }
+
+                        Some((
+                            (C::from_isize(ax)?, C::from_isize(ay)?),
+                            (C::from_isize(bx)?, C::from_isize(by)?),
+                            (dx.abs() + dy.abs()) as u32,
+                        ))
+                    })
+                })
+            })
+    }
+}
+
+#[inline]
+fn neighbor_indices(index: usize, w: usize, h: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = ((index % w) as isize, (index / w) as isize);
+    CARDINAL_STENCIL.into_iter().filter_map(move |(dx, dy)| {
+        let (nx, ny) = (x + dx, y + dy);
+        if nx >= 0 && ny >= 0 && (nx as usize) < w && (ny as usize) < h {
+            Some(ny as usize * w + nx as usize)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+S..#
+.#.#
+.#..
+...E
+";
+
+    #[test]
+    fn bfs_distances() {
+        let grid: Grid<(u8, u8), Vec<u8>, u8> = MAZE.parse().unwrap();
+        let from_start = grid.distance_map([(0, 0)], |c| *c != b'#');
+        assert_eq!(from_start[(3, 3)], 6);
+        assert_eq!(from_start[(2, 2)], 4);
+        assert_eq!(from_start[(3, 0)], UNREACHABLE);
+
+        let from_both = grid.distance_map([(0, 0), (3, 3)], |c| *c != b'#');
+        assert_eq!(from_both[(3, 3)], 0);
+        assert_eq!(from_both[(0, 3)], 3);
+        assert_eq!(from_both[(2, 0)], 2);
+    }
+
+    #[test]
+    fn weighted_distances() {
+        let grid: Grid<(u8, u8), Vec<u8>, u8> = "1191\n1911\n1111\n".parse().unwrap();
+        let distances = grid.distance_map_weighted([(0, 0)], |_, to| Some((to - b'0') as u32));
+        assert_eq!(distances[(3, 0)], 7);
+        assert_eq!(distances[(2, 0)], 10);
+
+        let walls = grid.distance_map_weighted([(0, 0)], |_, to| (*to == b'1').then_some(1));
+        assert_eq!(walls[(3, 0)], 7);
+        assert_eq!(walls[(1, 1)], UNREACHABLE);
+    }
+
+    #[test]
+    fn pairs_within_radius() {
+        let grid: Grid<(u8, u8), Vec<u8>, u8> = MAZE.parse().unwrap();
+        let distances = grid.distance_map([(0, 0)], |c| *c != b'#');
+        let pairs: Vec<_> = distances.pairs_within(2).collect();
+
+        assert!(pairs.contains(&((0, 0), (2, 0), 2)));
+        assert!(pairs.contains(&((2, 0), (0, 0), 2)));
+        assert!(!pairs.iter().any(|(a, b, _)| *a == (3, 0) || *b == (3, 0)));
+        assert!(pairs.iter().all(|(_, _, d)| (1..=2).contains(d)));
+
+        let grid: Grid<(u8, u8), Vec<u8>, u8> = "S#E\n.#.\n...\n".parse().unwrap();
+        let distances = grid.distance_map([(0, 0)], |c| *c != b'#');
+        let shortcuts = distances
+            .pairs_within(2)
+            .filter(|(a, b, d)| distances[*b] >= distances[*a] + d + 2)
+            .count();
+        assert_eq!(shortcuts, 2);
+    }
+}
@@ -0,0 +1,184 @@
+use super::Grid;
+use num::{FromPrimitive, ToPrimitive};
+
+/// A dense 2D grid that grows to fit whatever is written to it, in any direction. Cells
+/// outside the stored area read as the default.
+#[derive(Clone)]
+pub struct GrowingGrid<C, T> {
+    data: Vec<T>,
+    origin: (isize, isize),
+    size: (usize, usize),
+    default: T,
+    spooky_ghost: std::marker::PhantomData<C>,
+}
+
+impl<C, T> GrowingGrid<C, T>
+where
+    C: ToPrimitive + FromPrimitive,
+    T: Clone,
+{
+    pub fn new(default: T) -> Self {
+        Self {
+            data: Vec::new(),
+            origin: (0, 0),
+            size: (0, 0),
+            default,
+            spooky_ghost: Default::default(),
+        }
+    }
+
+    /// Start out with the area from `origin` and `size` already stored.
+    pub fn with_area(origin: (C, C), size: (usize, usize), default: T) -> Self {
+        Self {
+            data: vec![default.clone(); size.0 * size.1],
+            origin: (origin.0.to_isize().unwrap(), origin.1.to_isize().unwrap()),
+            size,
+            default,
+            spooky_ghost: Default::default(),
+        }
+    }
+
+    /// The top-left corner of the stored area.
+    #[inline]
+    pub fn origin(&self) -> (C, C) {
+        (
+            C::from_isize(self.origin.0).unwrap(),
+            C::from_isize(self.origin.1).unwrap(),
+        )
+    }
+
+    /// The size of the stored area, which includes some room to grow.
+    #[inline]
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    #[inline]
+    fn index_of(&self, pos: &(C, C)) -> Option<usize> {
+        let x = pos.0.to_isize()? - self.origin.0;
+        let y = pos.1.to_isize()? - self.origin.1;
+        if x >= 0 && y >= 0 && (x as usize) < self.size.0 && (y as usize) < self.size.1 {
+            Some(y as usize * self.size.0 + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// It's never out of bounds, so this always returns something.
+    #[inline]
+    pub fn cell(&self, pos: &(C, C)) -> Option<&T> {
+        match self.index_of(pos) {
+            Some(index) => Some(&self.data[index]),
+            None => Some(&self.default),
+        }
+    }
+
+    /// Get the cell for writing, growing the stored area if it's outside.
+    #[inline]
+    pub fn cell_mut(&mut self, pos: &(C, C)) -> Option<&mut T> {
+        if let Some(index) = self.index_of(pos) {
+            return Some(&mut self.data[index]);
+        }
+
+        self.grow_to(pos.0.to_isize()?, pos.1.to_isize()?);
+        let index = self.index_of(pos)?;
+        Some(&mut self.data[index])
+    }
+
+    /// Reset every cell to the default, keeping the stored area.
+    pub fn clear(&mut self) {
+        self.data.fill(self.default.clone());
+    }
+
+    /// Every cell in the stored area, including the ones that were never written.
+    pub fn iter(&self) -> impl Iterator<Item = ((C, C), &T)> {
+        let (w, (ox, oy)) = (self.size.0.max(1), self.origin);
+        self.data.iter().enumerate().filter_map(move |(i, cell)| {
+            let x = C::from_isize(ox + (i % w) as isize)?;
+            let y = C::from_isize(oy + (i / w) as isize)?;
+            Some(((x, y), cell))
+        })
+    }
+
+    /// Borrow the stored area as a regular grid, where `(0, 0)` is the origin.
+    pub fn as_grid(&self) -> Grid<(usize, usize), &[T], T> {
+        Grid::new_with_default(self.size, self.data.as_slice(), self.default.clone())
+    }
+
+    fn grow_to(&mut self, x: isize, y: isize) {
+        let (w, h) = (self.size.0 as isize, self.size.1 as isize);
+        let (mut x0, mut y0) = self.origin;
+        let (mut x1, mut y1) = (x0 + w, y0 + h);
+        if self.data.is_empty() {
+            (x0, y0, x1, y1) = (x, y, x + 1, y + 1);
+        }
+
+        // Grow by at least the current size each time, so it doesn't have to copy every
+        // time something walks off the edge.
+        let (pad_x, pad_y) = (w.max(4), h.max(4));
+        if x < x0 {
+            x0 = x.min(x0 - pad_x);
+        } else if x >= x1 {
+            x1 = (x + 1).max(x1 + pad_x);
+        }
+        if y < y0 {
+            y0 = y.min(y0 - pad_y);
+        } else if y >= y1 {
+            y1 = (y + 1).max(y1 + pad_y);
+        }
+
+        let (nw, nh) = ((x1 - x0) as usize, (y1 - y0) as usize);
+        let mut data = vec![self.default.clone(); nw * nh];
+        let (dx, dy) = ((self.origin.0 - x0) as usize, (self.origin.1 - y0) as usize);
+        for (row_index, row) in self.data.chunks_exact(self.size.0.max(1)).enumerate() {
+            let start = (row_index + dy) * nw + dx;
+            data[start..start + row.len()].clone_from_slice(row);
+        }
+
+        self.data = data;
+        self.origin = (x0, y0);
+        self.size = (nw, nh);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::SeenSpace;
+
+    #[test]
+    fn grows_in_every_direction() {
+        let mut grid = GrowingGrid::<i32, u8>::new(b'.');
+        *grid.cell_mut(&(0, 0)).unwrap() = b'a';
+        *grid.cell_mut(&(-7, 2)).unwrap() = b'b';
+        *grid.cell_mut(&(3, -20)).unwrap() = b'c';
+        *grid.cell_mut(&(40, 9)).unwrap() = b'd';
+
+        assert_eq!(grid.cell(&(0, 0)), Some(&b'a'));
+        assert_eq!(grid.cell(&(-7, 2)), Some(&b'b'));
+        assert_eq!(grid.cell(&(3, -20)), Some(&b'c'));
+        assert_eq!(grid.cell(&(40, 9)), Some(&b'd'));
+        assert_eq!(grid.cell(&(1000, 1000)), Some(&b'.'));
+        assert_eq!(grid.iter().filter(|(_, c)| **c != b'.').count(), 4);
+
+        let (ox, oy) = grid.origin();
+        let dense = grid.as_grid();
+        assert_eq!(
+            dense.cell(&((3 - ox) as usize, (-20 - oy) as usize)),
+            Some(&b'c')
+        );
+    }
+
+    #[test]
+    fn works_as_seen_space() {
+        let mut seen = GrowingGrid::<i64, u32>::new(0);
+        assert!(!seen.has_seen(&((-5i64, 5i64), 3u32)));
+        assert!(seen.try_mark_seen(((-5, 5), 3)));
+        assert!(seen.has_seen(&((-5, 5), 3)));
+        assert!(seen.try_mark_seen(((-5, 5), 2)));
+        assert!(!seen.try_mark_seen(((-5, 5), 9)));
+
+        SeenSpace::<((i64, i64), u32)>::reset(&mut seen);
+        assert!(!seen.has_seen(&((-5, 5), 3)));
+    }
+}
@@ -1,3 +1,22 @@
+mod bits;
+mod distance;
+mod growing;
+mod push;
+mod regions;
+mod render;
+mod sparse;
+mod transform;
+mod views;
+
+pub use bits::BitGrid;
+pub use distance::UNREACHABLE;
+pub use growing::GrowingGrid;
+pub use push::{PushCell, PushGrid, PushObject};
+pub use regions::{Region, Regions, NO_REGION};
+pub use render::{AnsiColor, GridChar, GridRender, ParseGridError};
+pub use sparse::SparseGrid;
+pub use views::{GridView, Ray, CARDINAL_STENCIL, NEIGHBOR_STENCIL};
+
 use std::ops::{Index, IndexMut};
 
 pub struct Grid<C, S, T>
@@ -0,0 +1,218 @@
+use super::Grid;
+use crate::utils::CardinalDirection;
+use std::collections::VecDeque;
+
+const EMPTY: u32 = u32::MAX;
+const WALL: u32 = u32::MAX - 1;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PushCell {
+    Empty,
+    Wall,
+    Object(usize),
+}
+
+/// A rectangle that takes up `size` cells, with its top left corner at `pos`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PushObject {
+    pub pos: (usize, usize),
+    pub size: (usize, usize),
+}
+
+impl PushObject {
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.size.1)
+            .flat_map(move |y| (0..self.size.0).map(move |x| (self.pos.0 + x, self.pos.1 + y)))
+    }
+}
+
+/// Sokoban-style physics: pushing an object shoves every object in front of it along,
+/// which for objects wider than one cell can fan out into a tree. Nothing moves unless
+/// all of them can, and walls or the edge stop everything. Every push is recorded so it
+/// can be undone.
+#[derive(Clone)]
+pub struct PushGrid {
+    cells: Grid<(usize, usize), Vec<u32>, u32>,
+    objects: Vec<PushObject>,
+    history: Vec<(Vec<usize>, CardinalDirection)>,
+}
+
+impl PushGrid {
+    pub fn new(size: (usize, usize)) -> Self {
+        Self {
+            cells: Grid::new_vec_initial(size, EMPTY),
+            objects: Vec::new(),
+            history: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn size(&self) -> (usize, usize) {
+        *self.cells.size()
+    }
+
+    pub fn add_wall(&mut self, pos: (usize, usize)) {
+        self.cells[pos] = WALL;
+    }
+
+    /// Add an object, returning its index. It's `None` if it doesn't fit where it's put.
+    pub fn add_object(&mut self, pos: (usize, usize), size: (usize, usize)) -> Option<usize> {
+        let object = PushObject { pos, size };
+        if object.cells().any(|p| self.cells.cell(&p) != Some(&EMPTY)) {
+            return None;
+        }
+
+        let index = self.objects.len();
+        for p in object.cells() {
+            self.cells[p] = index as u32;
+        }
+        self.objects.push(object);
+
+        Some(index)
+    }
+
+    pub fn at(&self, pos: (usize, usize)) -> PushCell {
+        match self.cells.cell(&pos).copied() {
+            Some(EMPTY) => PushCell::Empty,
+            Some(WALL) | None => PushCell::Wall,
+            Some(index) => PushCell::Object(index as usize),
+        }
+    }
+
+    #[inline]
+    pub fn object(&self, index: usize) -> &PushObject {
+        &self.objects[index]
+    }
+
+    #[inline]
+    pub fn objects(&self) -> &[PushObject] {
+        &self.objects
+    }
+
+    /// The objects that would move if the object got pushed, starting with it, or `None` if
+    /// something's in the way.
+    pub fn push_set(&self, index: usize, dir: CardinalDirection) -> Option<Vec<usize>> {
+        let mut moved = vec![index];
+        let mut queue = VecDeque::from([index]);
+        while let Some(current) = queue.pop_front() {
+            for pos in self.objects[current].cells() {
+                match self.at(dir.next_pos(&pos)) {
+                    PushCell::Empty => {}
+                    PushCell::Wall => return None,
+                    PushCell::Object(other) => {
+                        if !moved.contains(&other) {
+                            moved.push(other);
+                            queue.push_back(other);
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(moved)
+    }
+
+    /// Check if a push would go through, without doing it.
+    #[inline]
+    pub fn can_push(&self, index: usize, dir: CardinalDirection) -> bool {
+        self.push_set(index, dir).is_some()
+    }
+
+    /// Push the object, and everything in front of it. Returns false if it's blocked, which
+    /// still counts as a move for `undo`.
+    pub fn push(&mut self, index: usize, dir: CardinalDirection) -> bool {
+        let moved = self.push_set(index, dir).unwrap_or_default();
+        self.shift(&moved, dir);
+        let pushed = !moved.is_empty();
+        self.history.push((moved, dir));
+
+        pushed
+    }
+
+    /// Push the same object once per move, returning how many of them went through.
+    pub fn replay(&mut self, index: usize, moves: &[CardinalDirection]) -> usize {
+        moves.iter().filter(|dir| self.push(index, **dir)).count()
+    }
+
+    /// Take back the last push. Returns false if there's nothing left to undo.
+    pub fn undo(&mut self) -> bool {
+        if let Some((moved, dir)) = self.history.pop() {
+            self.shift(&moved, dir.turn_around());
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    fn shift(&mut self, moved: &[usize], dir: CardinalDirection) {
+        for index in moved.iter() {
+            for pos in self.objects[*index].cells() {
+                self.cells[pos] = EMPTY;
+            }
+        }
+
+        for index in moved.iter() {
+            let object = &mut self.objects[*index];
+            object.pos = dir.next_pos(&object.pos);
+            for pos in object.cells() {
+                self.cells[pos] = *index as u32;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CardinalDirection::*;
+
+    #[test]
+    fn pushes_trees() {
+        // ......
+        // .[][].
+        // ..[]..
+        // ..@...
+        let mut grid = PushGrid::new((6, 4));
+        let robot = grid.add_object((2, 3), (1, 1)).unwrap();
+        let middle = grid.add_object((2, 2), (2, 1)).unwrap();
+        let left = grid.add_object((1, 1), (2, 1)).unwrap();
+        let right = grid.add_object((3, 1), (2, 1)).unwrap();
+        assert_eq!(grid.add_object((3, 2), (2, 1)), None);
+
+        assert_eq!(
+            grid.push_set(robot, North),
+            Some(vec![robot, middle, left, right])
+        );
+        assert!(grid.push(robot, North));
+        assert_eq!(grid.object(right).pos, (3, 0));
+        assert_eq!(grid.at((2, 1)), PushCell::Object(middle));
+        assert!(!grid.can_push(robot, North));
+        assert!(!grid.push(robot, North));
+
+        grid.add_wall((0, 0));
+        assert!(!grid.can_push(left, West));
+        assert!(grid.can_push(right, East));
+
+        assert!(grid.undo());
+        assert!(grid.undo());
+        assert_eq!(grid.object(robot).pos, (2, 3));
+        assert_eq!(grid.object(left).pos, (1, 1));
+        assert!(!grid.undo());
+    }
+
+    #[test]
+    fn replays_moves() {
+        let mut grid = PushGrid::new((5, 1));
+        let robot = grid.add_object((0, 0), (1, 1)).unwrap();
+        grid.add_object((2, 0), (1, 1)).unwrap();
+        assert_eq!(grid.replay(robot, &[East, East, East, East, West]), 4);
+        assert_eq!(grid.object(robot).pos, (2, 0));
+        assert_eq!(grid.at((4, 0)), PushCell::Object(1));
+        assert_eq!(grid.history_len(), 5);
+    }
+}
@@ -0,0 +1,263 @@
+use super::{Grid, GridCoordinate, CARDINAL_STENCIL};
+use crate::search::{bfs, Order};
+use crate::utils::UnionFind;
+use bit_vec::BitVec;
+use num::{FromPrimitive, ToPrimitive};
+
+/// Label of the cells that aren't part of any region.
+pub const NO_REGION: usize = usize::MAX;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Region<C> {
+    pub area: usize,
+    /// The number of cell edges that face another region or the outside.
+    pub perimeter: usize,
+    /// The number of straight fence segments, which is the same as the number of corners.
+    pub sides: usize,
+    pub min: (C, C),
+    pub max: (C, C),
+}
+
+pub struct Regions<C>
+where
+    (C, C): GridCoordinate,
+{
+    /// The region index of every cell, or `NO_REGION`.
+    pub labels: Grid<(C, C), Vec<usize>, usize>,
+    pub regions: Vec<Region<C>>,
+}
+
+impl<C, S, T> Grid<(C, C), S, T>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive + FromPrimitive,
+    S: AsRef<[T]>,
+{
+    /// Split the grid into connected regions of equal cells.
+    pub fn regions(&self) -> Regions<C>
+    where
+        T: Eq,
+    {
+        self.label_regions(|_| true, |a, b| a == b)
+    }
+
+    /// Split the grid into connected regions, where neighbours that `same` returns true for
+    /// end up in the same one.
+    pub fn regions_by<F>(&self, same: F) -> Regions<C>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        self.label_regions(|_| true, same)
+    }
+
+    /// Split the cells that pass the predicate into connected regions. The rest are
+    /// labelled `NO_REGION`.
+    pub fn regions_where<F>(&self, pred: F) -> Regions<C>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.label_regions(&pred, |_, _| true)
+    }
+
+    /// Every cell reachable from `start` through cells that pass the predicate. The
+    /// mask is indexed the same way as the grid storage.
+    pub fn flood_fill<F>(&self, start: (C, C), passable: F) -> BitVec
+    where
+        F: Fn(&T) -> bool,
+    {
+        let data = self.storage.as_ref();
+        let (w, h) = (
+            self.size.0.to_usize().unwrap(),
+            self.size.1.to_usize().unwrap(),
+        );
+        let mut search = bfs().with_seen_space(BitVec::from_elem(w * h, false));
+        if self.cell(&start).is_some_and(&passable) {
+            search.push(start.index(&self.size));
+        }
+
+        search.find(|search, index: usize| {
+            let (x, y) = ((index % w) as isize, (index / w) as isize);
+            for (dx, dy) in CARDINAL_STENCIL {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx >= 0 && ny >= 0 && (nx as usize) < w && (ny as usize) < h {
+                    let next = ny as usize * w + nx as usize;
+                    if passable(&data[next]) {
+                        search.push(next);
+                    }
+                }
+            }
+
+            None::<()>
+        });
+
+        let (mut seen, _) = search.dissolve();
+        seen.grow((w * h).saturating_sub(seen.len()), false);
+        seen
+    }
+
+    fn label_regions<P, F>(&self, include: P, same: F) -> Regions<C>
+    where
+        P: Fn(&T) -> bool,
+        F: Fn(&T, &T) -> bool,
+    {
+        let data = self.storage.as_ref();
+        let (w, h) = (
+            self.size.0.to_usize().unwrap(),
+            self.size.1.to_usize().unwrap(),
+        );
+
+        let mut uf = UnionFind::new(w * h);
+        for y in 0..h {
+            for x in 0..w {
+                let i = y * w + x;
+                if !include(&data[i]) {
+                    continue;
+                }
+                if x + 1 < w && include(&data[i + 1]) && same(&data[i], &data[i + 1]) {
+                    uf.union(i, i + 1);
+                }
+                if y + 1 < h && include(&data[i + w]) && same(&data[i], &data[i + w]) {
+                    uf.union(i, i + w);
+                }
+            }
+        }
+
+        // Number the regions in the order their first cell shows up.
+        let mut root_labels = vec![NO_REGION; w * h];
+        let mut labels = vec![NO_REGION; w * h];
+        let mut regions: Vec<Region<C>> = Vec::new();
+        for (i, label) in labels.iter_mut().enumerate() {
+            if !include(&data[i]) {
+                continue;
+            }
+
+            let root = uf.find(i);
+            if root_labels[root] == NO_REGION {
+                root_labels[root] = regions.len();
+                regions.push(Region {
+                    area: 0,
+                    perimeter: 0,
+                    sides: 0,
+                    min: (C::from_usize(w).unwrap(), C::from_usize(h).unwrap()),
+                    max: (C::from_usize(0).unwrap(), C::from_usize(0).unwrap()),
+                });
+            }
+
+            *label = root_labels[root];
+        }
+
+        let same_region = |x: isize, y: isize, label: usize| {
+            x >= 0
+                && y >= 0
+                && (x as usize) < w
+                && (y as usize) < h
+                && labels[y as usize * w + x as usize] == label
+        };
+        let mut bounds = vec![(w, h, 0, 0); regions.len()];
+        for y in 0..h {
+            for x in 0..w {
+                let label = labels[y * w + x];
+                if label == NO_REGION {
+                    continue;
+                }
+
+                let region = &mut regions[label];
+                let (x, y) = (x as isize, y as isize);
+                region.area += 1;
+                for (dx, dy) in CARDINAL_STENCIL {
+                    if !same_region(x + dx, y + dy, label) {
+                        region.perimeter += 1;
+                    }
+                }
+                for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+                    let side_x = same_region(x + dx, y, label);
+                    let side_y = same_region(x, y + dy, label);
+                    let diagonal = same_region(x + dx, y + dy, label);
+                    if (!side_x && !side_y) || (side_x && side_y && !diagonal) {
+                        region.sides += 1;
+                    }
+                }
+
+                let b = &mut bounds[label];
+                let (x, y) = (x as usize, y as usize);
+                *b = (b.0.min(x), b.1.min(y), b.2.max(x), b.3.max(y));
+            }
+        }
+
+        for (region, (x0, y0, x1, y1)) in regions.iter_mut().zip(bounds) {
+            region.min = (C::from_usize(x0).unwrap(), C::from_usize(y0).unwrap());
+            region.max = (C::from_usize(x1).unwrap(), C::from_usize(y1).unwrap());
+        }
+
+        Regions {
+            labels: Grid::new_with_default(
+                (C::from_usize(w).unwrap(), C::from_usize(h).unwrap()),
+                labels,
+                NO_REGION,
+            ),
+            regions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid<(u8, u8), Vec<u8>, u8> {
+        s.parse().unwrap()
+    }
+
+    fn price(regions: &Regions<u8>, sides: bool) -> usize {
+        regions
+            .regions
+            .iter()
+            .map(|r| r.area * if sides { r.sides } else { r.perimeter })
+            .sum()
+    }
+
+    #[test]
+    fn day12_examples() {
+        let small = grid("AAAA\nBBCD\nBBCC\nEEEC\n").regions();
+        assert_eq!(small.regions.len(), 5);
+        assert_eq!(price(&small, false), 140);
+        assert_eq!(price(&small, true), 80);
+        assert_eq!(
+            small.regions[small.labels[(2, 1)]],
+            Region {
+                area: 4,
+                perimeter: 10,
+                sides: 8,
+                min: (2, 1),
+                max: (3, 3),
+            }
+        );
+
+        let nested = grid("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n").regions();
+        assert_eq!(price(&nested, true), 368);
+    }
+
+    #[test]
+    fn regions_where_skips_cells() {
+        let regions = grid("#..#\n#.##\n..#.\n").regions_where(|c| *c == b'#');
+        assert_eq!(regions.regions.len(), 2);
+        assert_eq!(regions.labels[(1, 0)], NO_REGION);
+        assert_eq!(regions.regions[regions.labels[(3, 0)]].area, 4);
+
+        let parity = grid("0123\n4567\n").regions_by(|a, b| a % 2 == b % 2);
+        assert_eq!(parity.regions.len(), 4);
+    }
+
+    #[test]
+    fn flood_fill_stays_inside_walls() {
+        let grid = grid("..#..\n..#..\n###..\n.....\n");
+        let mask = grid.flood_fill((0, 0), |c| *c == b'.');
+        assert_eq!(mask.len(), 20);
+        assert_eq!(mask.iter().filter(|b| *b).count(), 4);
+        assert!(mask[5] && !mask[3]);
+
+        let outside = grid.flood_fill((4, 3), |c| *c == b'.');
+        assert_eq!(outside.iter().filter(|b| *b).count(), 11);
+        assert!(grid.flood_fill((2, 0), |c| *c == b'.').none());
+    }
+}
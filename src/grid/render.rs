@@ -0,0 +1,421 @@
+use super::{Grid, GridCoordinate};
+use num::{FromPrimitive, ToPrimitive};
+use rustc_hash::FxHashMap;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+/// A cell that can be written as and read from a single character.
+pub trait GridChar: Sized {
+    fn to_char(&self) -> char;
+    fn from_char(c: char) -> Option<Self>;
+}
+
+impl GridChar for u8 {
+    #[inline]
+    fn to_char(&self) -> char {
+        *self as char
+    }
+
+    #[inline]
+    fn from_char(c: char) -> Option<Self> {
+        if c.is_ascii() {
+            Some(c as u8)
+        } else {
+            None
+        }
+    }
+}
+
+impl GridChar for char {
+    #[inline]
+    fn to_char(&self) -> char {
+        *self
+    }
+
+    #[inline]
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+impl GridChar for bool {
+    #[inline]
+    fn to_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    #[inline]
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AnsiColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl AnsiColor {
+    fn code(&self) -> u8 {
+        match self {
+            AnsiColor::Black => 30,
+            AnsiColor::Red => 31,
+            AnsiColor::Green => 32,
+            AnsiColor::Yellow => 33,
+            AnsiColor::Blue => 34,
+            AnsiColor::Magenta => 35,
+            AnsiColor::Cyan => 36,
+            AnsiColor::White => 37,
+        }
+    }
+}
+
+/// A view of a 2D grid as text. It's made with `Grid::render` and displays itself.
+pub struct GridRender<'g, C, S, T, F>
+where
+    (C, C): GridCoordinate,
+{
+    grid: &'g Grid<(C, C), S, T>,
+    cell_fn: F,
+    overlays: FxHashMap<(usize, usize), (char, Option<AnsiColor>)>,
+    min: (usize, usize),
+    max: (usize, usize),
+    rulers: bool,
+}
+
+impl<C, S, T> Grid<(C, C), S, T>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive,
+    S: AsRef<[T]>,
+{
+    /// Render each cell with the character from the callback.
+    pub fn render<F>(
+        &self,
+        f: F,
+    ) -> GridRender<'_, C, S, T, impl Fn(&T) -> (char, Option<AnsiColor>)>
+    where
+        F: Fn(&T) -> char,
+    {
+        self.render_colored(move |cell| (f(cell), None))
+    }
+
+    /// Render each cell with the character and, optionally, the colour from the callback.
+    pub fn render_colored<F>(&self, f: F) -> GridRender<'_, C, S, T, F>
+    where
+        F: Fn(&T) -> (char, Option<AnsiColor>),
+    {
+        GridRender {
+            grid: self,
+            cell_fn: f,
+            overlays: FxHashMap::default(),
+            min: (0, 0),
+            max: (
+                self.size.0.to_usize().unwrap(),
+                self.size.1.to_usize().unwrap(),
+            ),
+            rulers: false,
+        }
+    }
+}
+
+impl<'g, C, S, T, F> GridRender<'g, C, S, T, F>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive,
+    S: AsRef<[T]>,
+    F: Fn(&T) -> (char, Option<AnsiColor>),
+{
+    /// Draw the character on top of the cells at these positions. Later overlays are drawn
+    /// on top of earlier ones.
+    pub fn overlay<I>(self, points: I, c: char) -> Self
+    where
+        I: IntoIterator<Item = (C, C)>,
+    {
+        self.overlay_inner(points, c, None)
+    }
+
+    pub fn overlay_colored<I>(self, points: I, c: char, color: AnsiColor) -> Self
+    where
+        I: IntoIterator<Item = (C, C)>,
+    {
+        self.overlay_inner(points, c, Some(color))
+    }
+
+    /// Draw a path of adjacent points as arrows, with the last point marked by `*`.
+    pub fn overlay_path<I>(mut self, points: I, color: Option<AnsiColor>) -> Self
+    where
+        I: IntoIterator<Item = (C, C)>,
+    {
+        let mut prev: Option<(usize, usize)> = None;
+        for point in points {
+            let Some(current) = to_usize_pair(&point) else {
+                continue;
+            };
+
+            if let Some(prev) = prev {
+                let arrow = match (
+                    current.0 as isize - prev.0 as isize,
+                    current.1 as isize - prev.1 as isize,
+                ) {
+                    (0, -1) => '^',
+                    (1, 0) => '>',
+                    (0, 1) => 'v',
+                    (-1, 0) => '<',
+                    _ => '+',
+                };
+                self.overlays.insert(prev, (arrow, color));
+            }
+
+            self.overlays.insert(current, ('*', color));
+            prev = Some(current);
+        }
+
+        self
+    }
+
+    /// Only show the part of the grid starting at `origin` and extending `size`.
+    pub fn viewport(mut self, origin: (C, C), size: (C, C)) -> Self {
+        let (x, y) = to_usize_pair(&origin).unwrap_or((0, 0));
+        let (w, h) = to_usize_pair(&size).unwrap_or((0, 0));
+        let (gw, gh) = to_usize_pair(self.grid.size()).unwrap();
+
+        self.min = (x.min(gw), y.min(gh));
+        self.max = ((x + w).min(gw), (y + h).min(gh));
+        self
+    }
+
+    /// Add coordinate rulers above and to the left of the grid.
+    pub fn with_rulers(mut self) -> Self {
+        self.rulers = true;
+        self
+    }
+
+    fn overlay_inner<I>(mut self, points: I, c: char, color: Option<AnsiColor>) -> Self
+    where
+        I: IntoIterator<Item = (C, C)>,
+    {
+        for point in points {
+            if let Some(pos) = to_usize_pair(&point) {
+                self.overlays.insert(pos, (c, color));
+            }
+        }
+
+        self
+    }
+}
+
+impl<'g, C, S, T, F> Display for GridRender<'g, C, S, T, F>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive,
+    S: AsRef<[T]>,
+    F: Fn(&T) -> (char, Option<AnsiColor>),
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = self.grid.size.0.to_usize().unwrap();
+        let data = self.grid.storage.as_ref();
+        // Wide enough for the last row, but at least two digits.
+        let label_width = self.max.1.saturating_sub(1).max(10).ilog10() as usize + 1;
+        let pad = " ".repeat(label_width + 1);
+
+        if self.rulers {
+            write!(f, "{pad}")?;
+            for n in self.min.0..self.max.0 {
+                if n % 10 == 0 {
+                    write!(f, "{}", (n / 10) % 10)?;
+                } else {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f)?;
+            write!(f, "{pad}")?;
+            for n in self.min.0..self.max.0 {
+                write!(f, "{}", n % 10)?;
+            }
+            writeln!(f)?;
+        }
+
+        for y in self.min.1..self.max.1 {
+            if self.rulers {
+                write!(f, "{y:0label_width$} ")?;
+            }
+
+            for x in self.min.0..self.max.0 {
+                let (c, color) = match self.overlays.get(&(x, y)) {
+                    Some(overlay) => *overlay,
+                    None => (self.cell_fn)(&data[y * width + x]),
+                };
+
+                match color {
+                    Some(color) => write!(f, "\x1b[{}m{}\x1b[0m", color.code(), c)?,
+                    None => f.write_char(c)?,
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C, S, T> Display for Grid<(C, C), S, T>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive,
+    S: AsRef<[T]>,
+    T: GridChar,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|cell| cell.to_char()))
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseGridError {
+    /// A row didn't have the same length as the first one.
+    RaggedRow(usize),
+    /// The character at this position couldn't be turned into a cell.
+    BadChar(usize, usize, char),
+    /// The size does not fit the coordinate type.
+    TooLarge,
+}
+
+impl<C, T> FromStr for Grid<(C, C), Vec<T>, T>
+where
+    (C, C): GridCoordinate,
+    C: FromPrimitive,
+    T: GridChar + Default + Clone,
+{
+    type Err = ParseGridError;
+
+    /// Parse rows of characters, the way `Display` writes them. A trailing newline is allowed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_suffix('\n').unwrap_or(s);
+        let width = s.lines().next().map(|l| l.chars().count()).unwrap_or(0);
+        let mut data = Vec::with_capacity(s.len());
+        let mut height = 0;
+
+        for (y, line) in s.lines().enumerate() {
+            let mut row_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                data.push(T::from_char(c).ok_or(ParseGridError::BadChar(x, y, c))?);
+                row_width += 1;
+            }
+            if row_width != width {
+                return Err(ParseGridError::RaggedRow(y));
+            }
+
+            height += 1;
+        }
+
+        let size = (
+            C::from_usize(width).ok_or(ParseGridError::TooLarge)?,
+            C::from_usize(height).ok_or(ParseGridError::TooLarge)?,
+        );
+
+        Ok(Self::with_storage(size, data))
+    }
+}
+
+#[inline]
+fn to_usize_pair<C: ToPrimitive>(p: &(C, C)) -> Option<(usize, usize)> {
+    Some((p.0.to_usize()?, p.1.to_usize()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "\
+#######
+#S..#.#
+#.#...#
+#...#E#
+#######
+";
+
+    #[test]
+    fn display_round_trip() {
+        let grid: Grid<(u8, u8), Vec<u8>, u8> = MAP.parse().unwrap();
+        assert_eq!(*grid.size(), (7, 5));
+        assert_eq!(grid.cell(&(1, 1)), Some(&b'S'));
+        assert_eq!(grid.to_string(), MAP);
+
+        let walls: Grid<(usize, usize), Vec<bool>, bool> = "#.\n.#\n".parse().unwrap();
+        assert_eq!(walls.cell(&(1, 1)), Some(&true));
+        assert_eq!(walls.to_string(), "#.\n.#\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "##\n#\n".parse::<Grid<(u8, u8), Vec<u8>, u8>>().err(),
+            Some(ParseGridError::RaggedRow(1))
+        );
+        assert_eq!(
+            "#x\n".parse::<Grid<(u8, u8), Vec<bool>, bool>>().err(),
+            Some(ParseGridError::BadChar(1, 0, 'x'))
+        );
+        assert_eq!(
+            ".".repeat(300).parse::<Grid<(u8, u8), Vec<u8>, u8>>().err(),
+            Some(ParseGridError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn render_overlays_and_viewport() {
+        let grid: Grid<(u8, u8), Vec<u8>, u8> = MAP.parse().unwrap();
+        let text = grid
+            .render(|c| if *c == b'#' { '#' } else { ' ' })
+            .overlay_path([(1, 1), (2, 1), (3, 1), (3, 2)], None)
+            .overlay([(5, 3)], 'E')
+            .viewport((1, 1), (5, 3))
+            .to_string();
+
+        assert_eq!(text, ">>v# \n #*  \n   #E\n");
+    }
+
+    #[test]
+    fn render_colors_and_rulers() {
+        let grid: Grid<(u8, u8), Vec<u8>, u8> = "ab\ncd\n".parse().unwrap();
+        let text = grid
+            .render_colored(|c| (*c as char, (*c == b'a').then_some(AnsiColor::Red)))
+            .overlay_colored([(1, 1)], '@', AnsiColor::Green)
+            .with_rulers()
+            .to_string();
+
+        assert_eq!(
+            text,
+            "   0 \n   01\n00 \x1b[31ma\x1b[0mb\n01 c\x1b[32m@\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn rulers_fit_tall_grids() {
+        let grid: Grid<(u8, u8), Vec<u8>, u8> = "ab\n".repeat(120).parse().unwrap();
+        let text = grid.render(|c| *c as char).with_rulers().to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "    0 ");
+        assert_eq!(lines[1], "    01");
+        assert_eq!(lines[2], "000 ab");
+        assert_eq!(lines[121], "119 ab");
+    }
+}
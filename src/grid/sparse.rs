@@ -0,0 +1,145 @@
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+/// A grid without bounds that only stores the cells that have been written to. Every
+/// other cell reads as the default.
+#[derive(Clone)]
+pub struct SparseGrid<C, T> {
+    cells: FxHashMap<C, T>,
+    default: T,
+}
+
+impl<C, T> SparseGrid<C, T>
+where
+    C: Hash + Eq + Copy,
+{
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: FxHashMap::default(),
+            default,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize, default: T) -> Self {
+        let mut cells = FxHashMap::default();
+        cells.reserve(capacity);
+
+        Self { cells, default }
+    }
+
+    /// The number of cells that have been written to.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// It's never out of bounds, so this always returns something.
+    #[inline]
+    pub fn cell(&self, pos: &C) -> Option<&T> {
+        Some(self.cells.get(pos).unwrap_or(&self.default))
+    }
+
+    /// Only the stored cell, so it's `None` where `cell` would give the default.
+    #[inline]
+    pub fn get(&self, pos: &C) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    /// Get the cell for writing, storing a copy of the default if it wasn't there.
+    #[inline]
+    pub fn cell_mut(&mut self, pos: &C) -> Option<&mut T>
+    where
+        T: Clone,
+    {
+        Some(
+            self.cells
+                .entry(*pos)
+                .or_insert_with(|| self.default.clone()),
+        )
+    }
+
+    #[inline]
+    pub fn set(&mut self, pos: C, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    /// Forget the cell, so that it reads as the default again.
+    #[inline]
+    pub fn remove(&mut self, pos: &C) -> Option<T> {
+        self.cells.remove(pos)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// The stored cells, in no particular order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (C, &T)> {
+        self.cells.iter().map(|(pos, cell)| (*pos, cell))
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&C, &T) -> bool,
+    {
+        self.cells.retain(|pos, cell| f(pos, cell));
+    }
+}
+
+impl<C, T> SparseGrid<(C, C), T>
+where
+    C: Hash + Eq + Copy + Ord,
+{
+    /// The smallest and largest corners of the stored cells.
+    pub fn bounds(&self) -> Option<((C, C), (C, C))> {
+        let mut iter = self.cells.keys();
+        let first = *iter.next()?;
+
+        Some(iter.fold((first, first), |(min, max), (x, y)| {
+            (
+                (min.0.min(*x), min.1.min(*y)),
+                (max.0.max(*x), max.1.max(*y)),
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::SeenSpace;
+
+    #[test]
+    fn reads_default_and_tracks_bounds() {
+        let mut grid = SparseGrid::new(b'.');
+        assert_eq!(grid.cell(&(-1_000_000i64, 5)), Some(&b'.'));
+        assert_eq!(grid.bounds(), None);
+
+        grid.set((-3, 4), b'#');
+        *grid.cell_mut(&(10, -2)).unwrap() = b'O';
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.cell(&(10, -2)), Some(&b'O'));
+        assert_eq!(grid.bounds(), Some(((-3, -2), (10, 4))));
+
+        grid.remove(&(10, -2));
+        assert_eq!(grid.cell(&(10, -2)), Some(&b'.'));
+    }
+
+    #[test]
+    fn works_as_seen_space() {
+        let mut seen = SparseGrid::new(0u32);
+        assert!(seen.try_mark_seen(((1i32, -1i32), 5u32)));
+        assert!(seen.has_seen(&((1, -1), 7)));
+        assert!(!seen.has_seen(&((1, -1), 4)));
+        assert!(seen.try_mark_seen(((1, -1), 4)));
+        assert!(!seen.try_mark_seen(((1, -1), 4)));
+        assert_eq!(SeenSpace::<((i32, i32), u32)>::occupancy(&seen), Some(1));
+    }
+}
@@ -0,0 +1,188 @@
+use super::{Grid, GridCoordinate};
+use num::{FromPrimitive, ToPrimitive};
+
+impl<C, S, T> Grid<(C, C), S, T>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive + FromPrimitive,
+    S: AsRef<[T]>,
+    T: Clone,
+{
+    /// Build a new grid of the given size, taking each cell from the source index `f`
+    /// returns, or the default if it returns `None`.
+    fn remap<F>(&self, (w, h): (usize, usize), f: F) -> Grid<(C, C), Vec<T>, T>
+    where
+        F: Fn(usize, usize) -> Option<usize>,
+    {
+        let data = self.storage.as_ref();
+        let mut res = Vec::with_capacity(w * h);
+        for y in 0..h {
+            for x in 0..w {
+                match f(x, y) {
+                    Some(index) => res.push(data[index].clone()),
+                    None => res.push(self.default.clone()),
+                }
+            }
+        }
+
+        Grid::new_with_default(
+            (C::from_usize(w).unwrap(), C::from_usize(h).unwrap()),
+            res,
+            self.default.clone(),
+        )
+    }
+
+    #[inline]
+    fn dims(&self) -> (usize, usize) {
+        (
+            self.size.0.to_usize().unwrap(),
+            self.size.1.to_usize().unwrap(),
+        )
+    }
+
+    /// Rotate 90° clockwise.
+    pub fn rotated_cw(&self) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((h, w), |x, y| Some((h - 1 - x) * w + y))
+    }
+
+    /// Rotate 90° counter-clockwise.
+    pub fn rotated_ccw(&self) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((h, w), |x, y| Some(x * w + (w - 1 - y)))
+    }
+
+    pub fn rotated_180(&self) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((w, h), |x, y| Some((h - 1 - y) * w + (w - 1 - x)))
+    }
+
+    /// Mirror left to right.
+    pub fn flipped_horizontal(&self) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((w, h), |x, y| Some(y * w + (w - 1 - x)))
+    }
+
+    /// Mirror top to bottom.
+    pub fn flipped_vertical(&self) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((w, h), |x, y| Some((h - 1 - y) * w + x))
+    }
+
+    pub fn transposed(&self) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((h, w), |x, y| Some(x * w + y))
+    }
+
+    /// Add `n` cells of the grid's default value on every side.
+    pub fn padded(&self, n: usize) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((w + n * 2, h + n * 2), |x, y| {
+            let (x, y) = (x.checked_sub(n)?, y.checked_sub(n)?);
+            if x < w && y < h {
+                Some(y * w + x)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Copy out a rectangle of the grid. It's cut down to fit inside the grid.
+    pub fn cropped(&self, origin: (C, C), size: (C, C)) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        let ox = origin.0.to_usize().unwrap_or(0).min(w);
+        let oy = origin.1.to_usize().unwrap_or(0).min(h);
+        let cw = size.0.to_usize().unwrap_or(0).min(w - ox);
+        let ch = size.1.to_usize().unwrap_or(0).min(h - oy);
+
+        self.remap((cw, ch), |x, y| Some((y + oy) * w + (x + ox)))
+    }
+
+    /// Repeat the grid `nx` times across and `ny` times down.
+    pub fn tiled(&self, nx: usize, ny: usize) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((w * nx, h * ny), |x, y| Some((y % h) * w + (x % w)))
+    }
+
+    /// Blow up each cell into a `kx` by `ky` block of copies.
+    pub fn scaled(&self, kx: usize, ky: usize) -> Grid<(C, C), Vec<T>, T> {
+        let (w, h) = self.dims();
+        self.remap((w * kx, h * ky), |x, y| Some((y / ky) * w + (x / kx)))
+    }
+
+    /// Replace each cell with `K` cells side by side, like day 15's wide warehouse.
+    /// The default goes through `f` too, and the first of its cells is the new one. `K`
+    /// can't be 0, that doesn't compile.
+    pub fn widened<U, F, const K: usize>(&self, f: F) -> Grid<(C, C), Vec<U>, U>
+    where
+        U: Clone,
+        F: Fn(&T) -> [U; K],
+    {
+        const { assert!(K > 0, "can't widen cells to zero cells") };
+
+        let (w, h) = self.dims();
+        let mut res = Vec::with_capacity(w * h * K);
+        for cell in self.storage.as_ref()[..w * h].iter() {
+            res.extend(f(cell));
+        }
+
+        let default = f(&self.default).into_iter().next().unwrap();
+
+        Grid::new_with_default(
+            (C::from_usize(w * K).unwrap(), C::from_usize(h).unwrap()),
+            res,
+            default,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<(u8, u8), &'static [u8], u8> {
+        Grid::new_with_default((3, 2), b"abcdef", b'.')
+    }
+
+    fn text(grid: &Grid<(u8, u8), Vec<u8>, u8>) -> String {
+        grid.to_string()
+    }
+
+    #[test]
+    fn rotations() {
+        let grid = grid();
+        assert_eq!(text(&grid.rotated_cw()), "da\neb\nfc\n");
+        assert_eq!(text(&grid.rotated_ccw()), "cf\nbe\nad\n");
+        assert_eq!(text(&grid.rotated_180()), "fed\ncba\n");
+        assert_eq!(
+            text(&grid.rotated_cw().rotated_cw()),
+            text(&grid.rotated_180())
+        );
+        assert_eq!(text(&grid.rotated_cw().rotated_ccw()), "abc\ndef\n");
+    }
+
+    #[test]
+    fn flips_and_transpose() {
+        let grid = grid();
+        assert_eq!(text(&grid.flipped_horizontal()), "cba\nfed\n");
+        assert_eq!(text(&grid.flipped_vertical()), "def\nabc\n");
+        assert_eq!(text(&grid.transposed()), "ad\nbe\ncf\n");
+    }
+
+    #[test]
+    fn resizing() {
+        let grid = grid();
+        assert_eq!(text(&grid.padded(1)), ".....\n.abc.\n.def.\n.....\n");
+        assert_eq!(text(&grid.cropped((1, 0), (5, 1))), "bc\n");
+        assert_eq!(text(&grid.tiled(2, 2)), "abcabc\ndefdef\nabcabc\ndefdef\n");
+        assert_eq!(text(&grid.scaled(2, 1)), "aabbcc\nddeeff\n");
+        assert_eq!(
+            text(&grid.widened(|c| [*c, c.to_ascii_uppercase()])),
+            "aAbBcC\ndDeEfF\n"
+        );
+        assert_eq!(
+            text(&grid.widened(|c| [*c, *c]).padded(1)),
+            "........\n.aabbcc.\n.ddeeff.\n........\n"
+        );
+    }
+}
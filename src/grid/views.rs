@@ -0,0 +1,335 @@
+use super::{Grid, GridCoordinate};
+use num::{FromPrimitive, ToPrimitive};
+
+/// Offsets for the four cardinal neighbours, in the same order as `CardinalNeighbors`.
+pub const CARDINAL_STENCIL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+/// Offsets for all eight neighbours, in the same order as `Neighbors2D`.
+pub const NEIGHBOR_STENCIL: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl<C, S, T> Grid<(C, C), S, T>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive + FromPrimitive,
+    S: AsRef<[T]>,
+{
+    #[inline]
+    fn width(&self) -> usize {
+        self.size.0.to_usize().unwrap()
+    }
+
+    #[inline]
+    fn height(&self) -> usize {
+        self.size.1.to_usize().unwrap()
+    }
+
+    /// The row as a slice, or `None` if it's out of bounds.
+    #[inline]
+    pub fn row(&self, y: C) -> Option<&[T]> {
+        let (w, y) = (self.width(), y.to_usize()?);
+        if y < self.height() {
+            Some(&self.storage.as_ref()[y * w..(y + 1) * w])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        let w = self.width().max(1);
+        self.storage.as_ref()[..self.size.area()].chunks_exact(w)
+    }
+
+    /// Walk down the column. It's empty if `x` is out of bounds.
+    #[inline]
+    pub fn column(&self, x: C) -> impl Iterator<Item = &T> {
+        let (w, x) = (self.width(), x.to_usize().unwrap_or(usize::MAX));
+        let data = &self.storage.as_ref()[..self.size.area()];
+        let start = if x < w { x } else { data.len() };
+
+        data[start..].iter().step_by(w.max(1))
+    }
+
+    /// Walk from `start` in steps of `step` until it leaves the grid, including `start`
+    /// itself if it's in bounds.
+    #[inline]
+    pub fn ray(&self, start: (C, C), step: (isize, isize)) -> Ray<'_, C, T> {
+        Ray {
+            data: self.storage.as_ref(),
+            pos: (
+                start.0.to_isize().unwrap_or(-1),
+                start.1.to_isize().unwrap_or(-1),
+            ),
+            step,
+            size: (self.width() as isize, self.height() as isize),
+            spooky_ghost: Default::default(),
+        }
+    }
+
+    /// The diagonal going down and to the right from `start`.
+    #[inline]
+    pub fn diagonal(&self, start: (C, C)) -> Ray<'_, C, T> {
+        self.ray(start, (1, 1))
+    }
+
+    /// The diagonal going down and to the left from `start`.
+    #[inline]
+    pub fn anti_diagonal(&self, start: (C, C)) -> Ray<'_, C, T> {
+        self.ray(start, (-1, 1))
+    }
+
+    /// A rectangular part of the grid, cut down to fit inside it.
+    pub fn view(&self, origin: (C, C), size: (C, C)) -> GridView<'_, C, T> {
+        let (w, h) = (self.width(), self.height());
+        let x = origin.0.to_usize().unwrap_or(0).min(w);
+        let y = origin.1.to_usize().unwrap_or(0).min(h);
+        let vw = size.0.to_usize().unwrap_or(0).min(w - x);
+        let vh = size.1.to_usize().unwrap_or(0).min(h - y);
+
+        GridView {
+            data: self.storage.as_ref(),
+            stride: w,
+            offset: y * w + x,
+            size: (C::from_usize(vw).unwrap(), C::from_usize(vh).unwrap()),
+        }
+    }
+
+    /// The cardinal neighbours that are inside the grid.
+    #[inline]
+    pub fn neighbors(&self, pos: (C, C)) -> impl Iterator<Item = ((C, C), &T)> {
+        self.neighbors_stencil(pos, &CARDINAL_STENCIL)
+    }
+
+    /// All eight neighbours that are inside the grid.
+    #[inline]
+    pub fn neighbors_8(&self, pos: (C, C)) -> impl Iterator<Item = ((C, C), &T)> {
+        self.neighbors_stencil(pos, &NEIGHBOR_STENCIL)
+    }
+
+    /// The cells at each offset from `pos` that are inside the grid.
+    pub fn neighbors_stencil<'a>(
+        &'a self,
+        pos: (C, C),
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((C, C), &'a T)> + 'a {
+        let (w, h) = (self.width() as isize, self.height() as isize);
+        let x = pos.0.to_isize().unwrap_or(isize::MIN / 2);
+        let y = pos.1.to_isize().unwrap_or(isize::MIN / 2);
+        let data = self.storage.as_ref();
+
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx >= 0 && ny >= 0 && nx < w && ny < h {
+                Some((
+                    (C::from_isize(nx)?, C::from_isize(ny)?),
+                    &data[(ny * w + nx) as usize],
+                ))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<C, S, T> Grid<(C, C), S, T>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive,
+    S: AsMut<[T]>,
+{
+    #[inline]
+    pub fn row_mut(&mut self, y: C) -> Option<&mut [T]> {
+        let (w, h) = (
+            self.size.0.to_usize().unwrap(),
+            self.size.1.to_usize().unwrap(),
+        );
+        let y = y.to_usize()?;
+        if y < h {
+            Some(&mut self.storage.as_mut()[y * w..(y + 1) * w])
+        } else {
+            None
+        }
+    }
+}
+
+pub struct Ray<'g, C, T> {
+    data: &'g [T],
+    pos: (isize, isize),
+    step: (isize, isize),
+    size: (isize, isize),
+    spooky_ghost: std::marker::PhantomData<C>,
+}
+
+impl<'g, C, T> Iterator for Ray<'g, C, T>
+where
+    C: FromPrimitive,
+{
+    type Item = ((C, C), &'g T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.pos;
+        if x < 0 || y < 0 || x >= self.size.0 || y >= self.size.1 {
+            return None;
+        }
+
+        self.pos = (x + self.step.0, y + self.step.1);
+        if self.step == (0, 0) {
+            self.pos = (-1, -1);
+        }
+
+        Some((
+            (C::from_isize(x)?, C::from_isize(y)?),
+            &self.data[(y * self.size.0 + x) as usize],
+        ))
+    }
+}
+
+/// A borrowed rectangle of a grid. Positions are relative to its top-left corner.
+pub struct GridView<'g, C, T> {
+    data: &'g [T],
+    stride: usize,
+    offset: usize,
+    size: (C, C),
+}
+
+impl<'g, C, T> GridView<'g, C, T>
+where
+    (C, C): GridCoordinate,
+    C: ToPrimitive,
+{
+    #[inline]
+    pub fn size(&self) -> &(C, C) {
+        &self.size
+    }
+
+    #[inline]
+    pub fn cell(&self, pos: &(C, C)) -> Option<&'g T> {
+        if pos.in_bounds(&self.size) {
+            let (x, y) = (pos.0.to_usize()?, pos.1.to_usize()?);
+            self.data.get(self.offset + y * self.stride + x)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn row(&self, y: C) -> Option<&'g [T]> {
+        let (w, h) = (
+            self.size.0.to_usize().unwrap(),
+            self.size.1.to_usize().unwrap(),
+        );
+        let y = y.to_usize()?;
+        if y < h {
+            let start = self.offset + y * self.stride;
+            Some(&self.data[start..start + w])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'g [T]> + '_ {
+        let (w, h) = (
+            self.size.0.to_usize().unwrap(),
+            self.size.1.to_usize().unwrap(),
+        );
+
+        (0..h).map(move |y| {
+            let start = self.offset + y * self.stride;
+            &self.data[start..start + w]
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((C, C), &'g T)> + '_ {
+        let mut coord = <(C, C)>::zero();
+        let size = self.size;
+
+        self.rows().flatten().map(move |cell| {
+            let current = coord;
+            coord = coord.next(&size);
+            (current, cell)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"\
+abcd\
+efgh\
+ijkl";
+
+    fn grid() -> Grid<(u8, u8), &'static [u8], u8> {
+        Grid::with_storage((4, 3), DATA)
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), Some(&b"efgh"[..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), b"cgk");
+        assert_eq!(grid.column(4).count(), 0);
+
+        let mut owned = Grid::<(u8, u8), Vec<u8>, u8>::with_storage((4, 3), DATA.to_vec());
+        owned.row_mut(2).unwrap().fill(b'.');
+        assert_eq!(owned.row(2), Some(&b"...."[..]));
+    }
+
+    #[test]
+    fn rays_and_diagonals() {
+        let grid = grid();
+        let cells = |ray: Ray<'_, u8, u8>| ray.map(|(_, c)| *c).collect::<Vec<_>>();
+
+        assert_eq!(cells(grid.diagonal((0, 0))), b"afk");
+        assert_eq!(cells(grid.anti_diagonal((3, 0))), b"dgj");
+        assert_eq!(cells(grid.ray((3, 1), (-1, 0))), b"hgfe");
+        assert_eq!(cells(grid.ray((0, 0), (0, 0))), b"a");
+        assert_eq!(
+            grid.ray((1, 2), (0, -1)).map(|(p, _)| p).last(),
+            Some((1, 0))
+        );
+    }
+
+    #[test]
+    fn sub_grid_view() {
+        let grid = grid();
+        let view = grid.view((1, 1), (5, 5));
+        assert_eq!(*view.size(), (3, 2));
+        assert_eq!(view.cell(&(0, 0)), Some(&b'f'));
+        assert_eq!(view.cell(&(3, 0)), None);
+        assert_eq!(view.row(1), Some(&b"jkl"[..]));
+        assert_eq!(
+            view.iter().map(|(p, c)| (p, *c)).nth(4),
+            Some(((1, 1), b'k'))
+        );
+    }
+
+    #[test]
+    fn neighbors_respect_bounds() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbors((0, 0)).collect::<Vec<_>>(),
+            vec![((1, 0), &b'b'), ((0, 1), &b'e')]
+        );
+        assert_eq!(grid.neighbors_8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors_8((3, 2)).count(), 3);
+        assert_eq!(
+            grid.neighbors_stencil((0, 0), &[(2, 1), (-1, 0), (3, 2)])
+                .map(|(_, c)| *c)
+                .collect::<Vec<_>>(),
+            b"gl"
+        );
+    }
+}
@@ -1,13 +1,16 @@
 #![feature(portable_simd)]
 #![feature(cmp_minmax)]
 
+pub mod circuit;
 pub mod graph;
 pub mod grid;
 pub mod parser;
 pub mod utils;
+pub mod vm;
 
 #[macro_use]
 pub mod search;
 pub mod constants;
 pub mod point;
 pub mod runner;
+pub mod sim;
@@ -0,0 +1,155 @@
+use num::Signed;
+
+/// Neighbours on a hex grid, in axial `(q, r)` or cube `(q, r, s)` coordinates where
+/// `q + r + s = 0`. They go counter-clockwise from `+q`, the same for both forms.
+pub trait HexNeighbors: Copy {
+    fn hex_neighbors(&self) -> [Self; 6];
+}
+
+pub trait HexDistance: Copy {
+    type Component;
+
+    /// The number of steps between the two hexes.
+    fn hex_distance_to(&self, other: &Self) -> Self::Component;
+}
+
+const AXIAL_STEPS: [(i8, i8); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+#[inline]
+fn axial_step<T: Signed + Copy>(q: T, r: T, (dq, dr): (i8, i8)) -> (T, T) {
+    let step = |v: T, d: i8| match d {
+        1 => v + T::one(),
+        -1 => v - T::one(),
+        _ => v,
+    };
+
+    (step(q, dq), step(r, dr))
+}
+
+impl<T> HexNeighbors for (T, T)
+where
+    T: Signed + Copy,
+{
+    fn hex_neighbors(&self) -> [Self; 6] {
+        AXIAL_STEPS.map(|d| axial_step(self.0, self.1, d))
+    }
+}
+
+impl<T> HexNeighbors for [T; 2]
+where
+    T: Signed + Copy,
+{
+    fn hex_neighbors(&self) -> [Self; 6] {
+        AXIAL_STEPS.map(|d| {
+            let (q, r) = axial_step(self[0], self[1], d);
+            [q, r]
+        })
+    }
+}
+
+impl<T> HexNeighbors for (T, T, T)
+where
+    T: Signed + Copy,
+{
+    fn hex_neighbors(&self) -> [Self; 6] {
+        AXIAL_STEPS.map(|d| axial_to_cube(axial_step(self.0, self.1, d)))
+    }
+}
+
+impl<T> HexNeighbors for [T; 3]
+where
+    T: Signed + Copy,
+{
+    fn hex_neighbors(&self) -> [Self; 6] {
+        AXIAL_STEPS.map(|d| {
+            let (q, r, s) = axial_to_cube(axial_step(self[0], self[1], d));
+            [q, r, s]
+        })
+    }
+}
+
+impl<T> HexDistance for (T, T)
+where
+    T: Signed + Copy + Ord,
+{
+    type Component = T;
+
+    fn hex_distance_to(&self, other: &Self) -> T {
+        axial_to_cube(*self).hex_distance_to(&axial_to_cube(*other))
+    }
+}
+
+impl<T> HexDistance for [T; 2]
+where
+    T: Signed + Copy + Ord,
+{
+    type Component = T;
+
+    fn hex_distance_to(&self, other: &Self) -> T {
+        (self[0], self[1]).hex_distance_to(&(other[0], other[1]))
+    }
+}
+
+impl<T> HexDistance for (T, T, T)
+where
+    T: Signed + Copy + Ord,
+{
+    type Component = T;
+
+    fn hex_distance_to(&self, other: &Self) -> T {
+        let dq = (self.0 - other.0).abs();
+        let dr = (self.1 - other.1).abs();
+        let ds = (self.2 - other.2).abs();
+        dq.max(dr).max(ds)
+    }
+}
+
+impl<T> HexDistance for [T; 3]
+where
+    T: Signed + Copy + Ord,
+{
+    type Component = T;
+
+    fn hex_distance_to(&self, other: &Self) -> T {
+        (self[0], self[1], self[2]).hex_distance_to(&(other[0], other[1], other[2]))
+    }
+}
+
+#[inline]
+pub fn axial_to_cube<T: Signed + Copy>((q, r): (T, T)) -> (T, T, T) {
+    (q, r, -q - r)
+}
+
+#[inline]
+pub fn cube_to_axial<T: Copy>((q, r, _): (T, T, T)) -> (T, T) {
+    (q, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_are_one_step_away() {
+        let center = (2i32, -3i32);
+        for n in center.hex_neighbors() {
+            assert_eq!(center.hex_distance_to(&n), 1);
+        }
+
+        let cube = axial_to_cube(center);
+        assert_eq!(
+            cube.hex_neighbors().map(cube_to_axial),
+            center.hex_neighbors()
+        );
+        assert!(cube.hex_neighbors().iter().all(|(q, r, s)| q + r + s == 0));
+        assert_eq!([2, -3].hex_neighbors()[1], [3, -4]);
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!((0i32, 0i32).hex_distance_to(&(3, -1)), 3);
+        assert_eq!((0i32, 0i32).hex_distance_to(&(-2, -2)), 4);
+        assert_eq!([0i64, 0, 0].hex_distance_to(&[2, -5, 3]), 5);
+        assert_eq!([1i8, 1].hex_distance_to(&[1, 1]), 0);
+    }
+}
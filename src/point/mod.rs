@@ -0,0 +1,452 @@
+mod hex;
+mod vector;
+
+pub use hex::{axial_to_cube, cube_to_axial, HexDistance, HexNeighbors};
+pub use vector::{Point2, Point3};
+
+use num::traits::{WrappingAdd, WrappingSub};
+use num::One;
+use std::cmp::minmax;
+use std::ops::{Add, Mul, Sub};
+
+#[allow(dead_code)]
+pub trait CardinalNeighbors<T>: Copy {
+    fn cardinal_neighbors(&self) -> [Self; 4];
+    fn cardinal_neighbors_n(&self, n: T) -> [Self; 4];
+}
+
+impl<T> CardinalNeighbors<T> for (T, T)
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn cardinal_neighbors(&self) -> [Self; 4] {
+        self.cardinal_neighbors_n(T::one())
+    }
+
+    fn cardinal_neighbors_n(&self, n: T) -> [Self; 4] {
+        let (x, y) = *self;
+        [(x, y - n), (x - n, y), (x + n, y), (x, y + n)]
+    }
+}
+
+impl<T> CardinalNeighbors<T> for [T; 2]
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn cardinal_neighbors(&self) -> [Self; 4] {
+        self.cardinal_neighbors_n(T::one())
+    }
+
+    fn cardinal_neighbors_n(&self, n: T) -> [Self; 4] {
+        let [x, y] = *self;
+        [[x, y - n], [x - n, y], [x + n, y], [x, y + n]]
+    }
+}
+
+#[allow(dead_code)]
+pub trait CardinalNeighborsWrapping<T>: Copy {
+    fn cardinal_neighbors_wrapping(&self) -> [Self; 4];
+    fn cardinal_neighbors_wrapping_n(&self, n: T) -> [Self; 4];
+}
+
+impl<T> CardinalNeighborsWrapping<T> for (T, T)
+where
+    T: Copy + WrappingAdd<Output = T> + WrappingSub<Output = T> + One,
+{
+    fn cardinal_neighbors_wrapping(&self) -> [Self; 4] {
+        self.cardinal_neighbors_wrapping_n(T::one())
+    }
+
+    fn cardinal_neighbors_wrapping_n(&self, n: T) -> [Self; 4] {
+        let (x, y) = *self;
+        [
+            (x, y.wrapping_sub(&n)),
+            (x.wrapping_sub(&n), y),
+            (x.wrapping_add(&n), y),
+            (x, y.wrapping_add(&n)),
+        ]
+    }
+}
+
+impl<T> CardinalNeighborsWrapping<T> for [T; 2]
+where
+    T: Copy + WrappingAdd<Output = T> + WrappingSub<Output = T> + One,
+{
+    fn cardinal_neighbors_wrapping(&self) -> [Self; 4] {
+        self.cardinal_neighbors_wrapping_n(T::one())
+    }
+
+    fn cardinal_neighbors_wrapping_n(&self, n: T) -> [Self; 4] {
+        let [x, y] = *self;
+        [
+            [x, y.wrapping_sub(&n)],
+            [x.wrapping_sub(&n), y],
+            [x.wrapping_add(&n), y],
+            [x, y.wrapping_add(&n)],
+        ]
+    }
+}
+
+#[allow(dead_code)]
+pub trait XNeighbors: Copy {
+    fn x_neighbors(&self) -> [Self; 4];
+}
+
+impl<T> XNeighbors for (T, T)
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn x_neighbors(&self) -> [Self; 4] {
+        let one = T::one();
+        let (x, y) = *self;
+
+        [
+            (x - one, y - one),
+            (x + one, y - one),
+            (x - one, y + one),
+            (x + one, y + one),
+        ]
+    }
+}
+
+impl<T> XNeighbors for [T; 2]
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn x_neighbors(&self) -> [Self; 4] {
+        let one = T::one();
+        let [x, y] = *self;
+
+        [
+            [x - one, y - one],
+            [x + one, y - one],
+            [x - one, y + one],
+            [x + one, y + one],
+        ]
+    }
+}
+
+#[allow(dead_code)]
+pub trait Neighbors2D: Copy {
+    fn neighbors_2d(&self) -> [Self; 8];
+}
+
+impl<T> Neighbors2D for (T, T)
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn neighbors_2d(&self) -> [Self; 8] {
+        let one = T::one();
+        let (x, y) = *self;
+
+        [
+            (x - one, y - one),
+            (x, y - one),
+            (x + one, y - one),
+            (x - one, y),
+            (x + one, y),
+            (x - one, y + one),
+            (x, y + one),
+            (x + one, y + one),
+        ]
+    }
+}
+
+impl<T> Neighbors2D for [T; 2]
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn neighbors_2d(&self) -> [Self; 8] {
+        let one = T::one();
+        let [x, y] = *self;
+
+        [
+            [x - one, y - one],
+            [x, y - one],
+            [x + one, y - one],
+            [x - one, y],
+            [x + one, y],
+            [x - one, y + one],
+            [x, y + one],
+            [x + one, y + one],
+        ]
+    }
+}
+
+/// Step a component by the offset -1, 0 or 1 for `d` = 0, 1 or 2.
+#[inline]
+fn offset<T>(v: T, d: usize) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    match d {
+        0 => v - T::one(),
+        1 => v,
+        _ => v + T::one(),
+    }
+}
+
+/// The index of the `i`th neighbour in a 3^n cube of offsets, skipping the middle.
+#[inline]
+fn skip_middle(i: usize, middle: usize) -> usize {
+    if i < middle {
+        i
+    } else {
+        i + 1
+    }
+}
+
+#[allow(dead_code)]
+pub trait Neighbors3D: Copy {
+    /// The face neighbours, in index order.
+    fn neighbors_6(&self) -> [Self; 6];
+    /// Every neighbour that shares a face, edge or corner, in index order.
+    fn neighbors_26(&self) -> [Self; 26];
+}
+
+impl<T> Neighbors3D for (T, T, T)
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn neighbors_6(&self) -> [Self; 6] {
+        let one = T::one();
+        let (x, y, z) = *self;
+
+        [
+            (x, y, z - one),
+            (x, y - one, z),
+            (x - one, y, z),
+            (x + one, y, z),
+            (x, y + one, z),
+            (x, y, z + one),
+        ]
+    }
+
+    fn neighbors_26(&self) -> [Self; 26] {
+        let (x, y, z) = *self;
+        std::array::from_fn(|i| {
+            let i = skip_middle(i, 13);
+            (offset(x, i % 3), offset(y, i / 3 % 3), offset(z, i / 9))
+        })
+    }
+}
+
+impl<T> Neighbors3D for [T; 3]
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn neighbors_6(&self) -> [Self; 6] {
+        let (x, y, z) = (self[0], self[1], self[2]);
+        (x, y, z).neighbors_6().map(|(x, y, z)| [x, y, z])
+    }
+
+    fn neighbors_26(&self) -> [Self; 26] {
+        let (x, y, z) = (self[0], self[1], self[2]);
+        (x, y, z).neighbors_26().map(|(x, y, z)| [x, y, z])
+    }
+}
+
+#[allow(dead_code)]
+pub trait Neighbors4D: Copy {
+    /// The neighbours one step along a single axis, in index order.
+    fn neighbors_8(&self) -> [Self; 8];
+    /// Every neighbour within one step along each axis, in index order.
+    fn neighbors_80(&self) -> [Self; 80];
+}
+
+impl<T> Neighbors4D for (T, T, T, T)
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn neighbors_8(&self) -> [Self; 8] {
+        let one = T::one();
+        let (x, y, z, w) = *self;
+
+        [
+            (x, y, z, w - one),
+            (x, y, z - one, w),
+            (x, y - one, z, w),
+            (x - one, y, z, w),
+            (x + one, y, z, w),
+            (x, y + one, z, w),
+            (x, y, z + one, w),
+            (x, y, z, w + one),
+        ]
+    }
+
+    fn neighbors_80(&self) -> [Self; 80] {
+        let (x, y, z, w) = *self;
+        std::array::from_fn(|i| {
+            let i = skip_middle(i, 40);
+            (
+                offset(x, i % 3),
+                offset(y, i / 3 % 3),
+                offset(z, i / 9 % 3),
+                offset(w, i / 27),
+            )
+        })
+    }
+}
+
+impl<T> Neighbors4D for [T; 4]
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + One,
+{
+    fn neighbors_8(&self) -> [Self; 8] {
+        let (x, y, z, w) = (self[0], self[1], self[2], self[3]);
+        (x, y, z, w).neighbors_8().map(|(x, y, z, w)| [x, y, z, w])
+    }
+
+    fn neighbors_80(&self) -> [Self; 80] {
+        let (x, y, z, w) = (self[0], self[1], self[2], self[3]);
+        (x, y, z, w).neighbors_80().map(|(x, y, z, w)| [x, y, z, w])
+    }
+}
+
+pub trait ManhattanDistance: Copy {
+    type Component;
+
+    fn manhattan_distance_to(&self, other: &Self) -> Self::Component;
+}
+
+impl<C> ManhattanDistance for (C, C)
+where
+    C: Copy + Ord + Add<Output = C> + Sub<Output = C>,
+{
+    type Component = C;
+
+    fn manhattan_distance_to(&self, other: &Self) -> Self::Component {
+        let [x1, x2] = minmax(self.0, other.0);
+        let [y1, y2] = minmax(self.1, other.1);
+        (x2 - x1) + (y2 - y1)
+    }
+}
+
+impl<C> ManhattanDistance for (C, C, C)
+where
+    C: Copy + Ord + Add<Output = C> + Sub<Output = C>,
+{
+    type Component = C;
+
+    fn manhattan_distance_to(&self, other: &Self) -> Self::Component {
+        let [x1, x2] = minmax(self.0, other.0);
+        let [y1, y2] = minmax(self.1, other.1);
+        let [z1, z2] = minmax(self.2, other.2);
+        (x2 - x1) + (y2 - y1) + (z2 - z1)
+    }
+}
+
+pub fn manhattan_distance<C, P: ManhattanDistance<Component = C>>(p1: &P, p2: &P) -> C {
+    p1.manhattan_distance_to(p2)
+}
+
+pub trait ChebyshevDistance: Copy {
+    type Component;
+
+    /// The largest difference along any axis, which is the number of king moves.
+    fn chebyshev_distance_to(&self, other: &Self) -> Self::Component;
+}
+
+pub trait EuclideanDistanceSquared: Copy {
+    type Component;
+
+    /// The squared straight-line distance, which stays exact for integers.
+    fn euclidean_distance_squared_to(&self, other: &Self) -> Self::Component;
+}
+
+macro_rules! impl_distances {
+    ($tuple: ty, $array: ty, $($i: tt),+) => {
+        impl<C> ChebyshevDistance for $tuple
+        where
+            C: Copy + Ord + Sub<Output = C>,
+        {
+            type Component = C;
+
+            fn chebyshev_distance_to(&self, other: &Self) -> C {
+                [$({
+                    let [a, b] = minmax(self.$i, other.$i);
+                    b - a
+                }),+]
+                .into_iter()
+                .max()
+                .unwrap()
+            }
+        }
+
+        impl<C> ChebyshevDistance for $array
+        where
+            C: Copy + Ord + Sub<Output = C>,
+        {
+            type Component = C;
+
+            fn chebyshev_distance_to(&self, other: &Self) -> C {
+                ($(self[$i]),+).chebyshev_distance_to(&($(other[$i]),+))
+            }
+        }
+
+        impl<C> EuclideanDistanceSquared for $tuple
+        where
+            C: Copy + Ord + Add<Output = C> + Sub<Output = C> + Mul<Output = C>,
+        {
+            type Component = C;
+
+            fn euclidean_distance_squared_to(&self, other: &Self) -> C {
+                [$({
+                    let [a, b] = minmax(self.$i, other.$i);
+                    (b - a) * (b - a)
+                }),+]
+                .into_iter()
+                .reduce(|acc, d| acc + d)
+                .unwrap()
+            }
+        }
+
+        impl<C> EuclideanDistanceSquared for $array
+        where
+            C: Copy + Ord + Add<Output = C> + Sub<Output = C> + Mul<Output = C>,
+        {
+            type Component = C;
+
+            fn euclidean_distance_squared_to(&self, other: &Self) -> C {
+                ($(self[$i]),+).euclidean_distance_squared_to(&($(other[$i]),+))
+            }
+        }
+    };
+}
+
+impl_distances!((C, C), [C; 2], 0, 1);
+impl_distances!((C, C, C), [C; 3], 0, 1, 2);
+impl_distances!((C, C, C, C), [C; 4], 0, 1, 2, 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_3d_and_4d() {
+        let n6 = (5i32, 5, 5).neighbors_6();
+        assert!(n6.iter().all(|n| n.manhattan_distance_to(&(5, 5, 5)) == 1));
+
+        let n26 = [1u8, 1, 1].neighbors_26();
+        assert_eq!(n26[0], [0, 0, 0]);
+        assert_eq!(n26[25], [2, 2, 2]);
+        assert!(!n26.contains(&[1, 1, 1]));
+        assert!(n26.iter().all(|n| n.chebyshev_distance_to(&[1, 1, 1]) == 1));
+
+        let n80 = (1i16, 1, 1, 1).neighbors_80();
+        assert!(!n80.contains(&(1, 1, 1, 1)));
+        assert_eq!(n80[79], (2, 2, 2, 2));
+        assert_eq!([0i8, 0, 0, 0].neighbors_8()[4], [1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!((1i32, 2).chebyshev_distance_to(&(-3, 4)), 4);
+        assert_eq!([1u32, 9, 4].chebyshev_distance_to(&[3, 2, 4]), 7);
+        assert_eq!((0i64, 0).euclidean_distance_squared_to(&(3, -4)), 25);
+        assert_eq!(
+            [1u8, 2, 3, 4].euclidean_distance_squared_to(&[2, 2, 5, 1]),
+            14
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use super::ManhattanDistance;
+use crate::grid::GridCoordinate;
+use crate::search::Key;
+use crate::utils::CardinalDirection;
+use num::traits::{Euclid, WrappingSub};
+use num::{One, Signed, Zero};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A 2D point or vector, with `y` growing downwards like in the grids.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    #[inline]
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product, which is positive if `other` is
+    /// clockwise from `self` on screen.
+    #[inline]
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    /// Turn 90° clockwise, as seen on screen.
+    #[inline]
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Turn 90° counter-clockwise, as seen on screen.
+    #[inline]
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Euclid,
+{
+    /// Wrap the point around into `0..size` on both axes.
+    #[inline]
+    pub fn rem_euclid(&self, size: &Self) -> Self {
+        Self::new(self.x.rem_euclid(&size.x), self.y.rem_euclid(&size.y))
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + WrappingSub + Add<Output = T> + One,
+{
+    /// One step in the direction. It wraps instead of going below zero, so it works for
+    /// unsigned coordinates and `Grid::cell` will just see it as out of bounds.
+    #[inline]
+    pub fn step(&self, dir: CardinalDirection) -> Self {
+        dir.next_pos(&(self.x, self.y)).into()
+    }
+}
+
+impl<T> From<CardinalDirection> for Point2<T>
+where
+    T: Signed,
+{
+    fn from(dir: CardinalDirection) -> Self {
+        match dir {
+            CardinalDirection::West => Self::new(-T::one(), T::zero()),
+            CardinalDirection::North => Self::new(T::zero(), -T::one()),
+            CardinalDirection::East => Self::new(T::one(), T::zero()),
+            CardinalDirection::South => Self::new(T::zero(), T::one()),
+        }
+    }
+}
+
+impl<T> Point3<T> {
+    #[inline]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    #[inline]
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Euclid,
+{
+    #[inline]
+    pub fn rem_euclid(&self, size: &Self) -> Self {
+        Self::new(
+            self.x.rem_euclid(&size.x),
+            self.y.rem_euclid(&size.y),
+            self.z.rem_euclid(&size.z),
+        )
+    }
+}
+
+macro_rules! impl_point_ops {
+    ($name: ident, $($f: ident),+) => {
+        impl<T> Add for $name<T>
+        where
+            T: Add<Output = T>,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                Self { $($f: self.$f + rhs.$f),+ }
+            }
+        }
+
+        impl<T> Sub for $name<T>
+        where
+            T: Sub<Output = T>,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($f: self.$f - rhs.$f),+ }
+            }
+        }
+
+        impl<T> AddAssign for $name<T>
+        where
+            T: AddAssign,
+        {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$f += rhs.$f;)+
+            }
+        }
+
+        impl<T> SubAssign for $name<T>
+        where
+            T: SubAssign,
+        {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$f -= rhs.$f;)+
+            }
+        }
+
+        impl<T> Mul<T> for $name<T>
+        where
+            T: Copy + Mul<Output = T>,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: T) -> Self {
+                Self { $($f: self.$f * rhs),+ }
+            }
+        }
+
+        impl<T> Neg for $name<T>
+        where
+            T: Neg<Output = T>,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self { $($f: -self.$f),+ }
+            }
+        }
+
+        impl<T> Zero for $name<T>
+        where
+            T: Zero,
+        {
+            #[inline]
+            fn zero() -> Self {
+                Self { $($f: T::zero()),+ }
+            }
+
+            #[inline]
+            fn is_zero(&self) -> bool {
+                $(self.$f.is_zero())&&+
+            }
+        }
+
+        impl<T> Key<$name<T>> for $name<T>
+        where
+            T: Copy,
+        {
+            #[inline]
+            fn key(&self) -> $name<T> {
+                *self
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2, x, y);
+impl_point_ops!(Point3, x, y, z);
+
+impl<T> From<(T, T)> for Point2<T> {
+    #[inline]
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    #[inline]
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<[T; 2]> for Point2<T> {
+    #[inline]
+    fn from([x, y]: [T; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    #[inline]
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    #[inline]
+    fn from(p: Point3<T>) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+impl<T> From<[T; 3]> for Point3<T> {
+    #[inline]
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> Key<(T, T)> for Point2<T>
+where
+    T: Copy,
+{
+    #[inline]
+    fn key(&self) -> (T, T) {
+        (self.x, self.y)
+    }
+}
+
+impl<T> Key<(T, T, T)> for Point3<T>
+where
+    T: Copy,
+{
+    #[inline]
+    fn key(&self) -> (T, T, T) {
+        (self.x, self.y, self.z)
+    }
+}
+
+impl<T> ManhattanDistance for Point2<T>
+where
+    (T, T): ManhattanDistance,
+    T: Copy,
+{
+    type Component = <(T, T) as ManhattanDistance>::Component;
+
+    #[inline]
+    fn manhattan_distance_to(&self, other: &Self) -> Self::Component {
+        (self.x, self.y).manhattan_distance_to(&(other.x, other.y))
+    }
+}
+
+impl<T> ManhattanDistance for Point3<T>
+where
+    (T, T, T): ManhattanDistance,
+    T: Copy,
+{
+    type Component = <(T, T, T) as ManhattanDistance>::Component;
+
+    #[inline]
+    fn manhattan_distance_to(&self, other: &Self) -> Self::Component {
+        (self.x, self.y, self.z).manhattan_distance_to(&(other.x, other.y, other.z))
+    }
+}
+
+/// Works the same as the tuple, so a `Grid<Point2<u8>, ..>` is laid out like a
+/// `Grid<(u8, u8), ..>`.
+impl<T> GridCoordinate for Point2<T>
+where
+    (T, T): GridCoordinate,
+    T: Copy + Default,
+{
+    #[inline]
+    fn zero() -> Self {
+        <(T, T)>::zero().into()
+    }
+
+    #[inline]
+    fn area(&self) -> usize {
+        (self.x, self.y).area()
+    }
+
+    #[inline]
+    fn in_bounds(&self, size: &Self) -> bool {
+        (self.x, self.y).in_bounds(&(size.x, size.y))
+    }
+
+    #[inline]
+    fn index(&self, size: &Self) -> usize {
+        (self.x, self.y).index(&(size.x, size.y))
+    }
+
+    #[inline]
+    fn next(&self, size: &Self) -> Self {
+        (self.x, self.y).next(&(size.x, size.y)).into()
+    }
+}
+
+impl<T> GridCoordinate for Point3<T>
+where
+    (T, T, T): GridCoordinate,
+    T: Copy + Default,
+{
+    #[inline]
+    fn zero() -> Self {
+        <(T, T, T)>::zero().into()
+    }
+
+    #[inline]
+    fn area(&self) -> usize {
+        (self.x, self.y, self.z).area()
+    }
+
+    #[inline]
+    fn in_bounds(&self, size: &Self) -> bool {
+        (self.x, self.y, self.z).in_bounds(&(size.x, size.y, size.z))
+    }
+
+    #[inline]
+    fn index(&self, size: &Self) -> usize {
+        (self.x, self.y, self.z).index(&(size.x, size.y, size.z))
+    }
+
+    #[inline]
+    fn next(&self, size: &Self) -> Self {
+        (self.x, self.y, self.z)
+            .next(&(size.x, size.y, size.z))
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn arithmetic_and_rotation() {
+        let p = Point2::new(3i32, -2);
+        let q = Point2::from((1, 4));
+        assert_eq!(p + q, Point2::new(4, 2));
+        assert_eq!(p - q, Point2::new(2, -6));
+        assert_eq!(p * 3, Point2::new(9, -6));
+        assert_eq!(p.dot(&q), -5);
+        assert_eq!(p.cross(&q), 14);
+        assert_eq!(p.rotate_right().rotate_left(), p);
+        assert_eq!(
+            Point2::<i32>::from(CardinalDirection::North).rotate_right(),
+            Point2::from(CardinalDirection::East)
+        );
+        assert_eq!(
+            Point2::new(-3i32, 12).rem_euclid(&Point2::new(11, 7)),
+            Point2::new(8, 5)
+        );
+        assert_eq!(<(i32, i32)>::from(-p), (-3, 2));
+
+        let x = Point3::new(1i64, 0, 0);
+        assert_eq!(x.cross(&Point3::new(0, 1, 0)), Point3::new(0, 0, 1));
+        assert_eq!(x.manhattan_distance_to(&Point3::new(4, -1, 2)), 6);
+    }
+
+    #[test]
+    fn grid_and_direction_integration() {
+        let grid = Grid::<Point2<u8>, &[u8], u8>::with_storage(Point2::new(3, 2), b"abcdef");
+        let pos = Point2::new(1u8, 0u8);
+        assert_eq!(grid.cell(&pos.step(CardinalDirection::South)), Some(&b'e'));
+        assert_eq!(grid.cell(&pos.step(CardinalDirection::North)), None);
+        assert_eq!(grid.iter().map(|(p, _)| p).last(), Some(Point2::new(2, 1)));
+        assert_eq!(Key::<(u8, u8)>::key(&pos), (1, 0));
+    }
+}
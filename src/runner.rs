@@ -65,7 +65,7 @@ impl Runner {
 
     pub fn is_cold(&self) -> bool {
         match self.op.as_str() {
-            "once" | "table_once" => true,
+            "once" | "table_once" | "dot" => true,
             _ => false,
         }
     }
@@ -106,6 +106,13 @@ impl Runner {
                     println!("Total: {}", format_duration(shortest));
                 }
             }
+            "dot" => {
+                let dot = self.graph.to_dot(
+                    |_, run| format!("{}\n{}", run.name, format_duration(run.duration_ns)),
+                    |_| String::new(),
+                );
+                print!("{}", dot);
+            }
             _ => {
                 panic!("Unknown op: {}", self.op);
             }
@@ -2,6 +2,7 @@
 mod attributes;
 mod order;
 mod seen;
+mod stats;
 
 use std::marker::PhantomData;
 use std::ops::Add;
@@ -15,21 +16,54 @@ use crate::utils::GatherTarget;
 pub use attributes::{Cost, Key, OnlyKey, KE};
 pub use order::Order;
 pub use seen::{BitArrSeenSpace, NoSeenSpace, ReEntrantSeenMap, SeenSpace};
+pub use stats::{NoStats, SearchStats, StatsCollector};
 
-pub struct Search<S, SEEN, ORDER>
+pub struct Search<S, SEEN, ORDER, STATS = NoStats>
 where
     SEEN: SeenSpace<S>,
     ORDER: Order<S>,
+    STATS: StatsCollector,
 {
     seen: SEEN,
     order: ORDER,
+    stats: STATS,
     spooky_ghost: PhantomData<S>,
 }
 
-impl<S, SEEN, ORDER> Search<S, SEEN, ORDER>
+impl<S, SEEN, ORDER> Search<S, SEEN, ORDER, NoStats>
 where
     SEEN: SeenSpace<S>,
     ORDER: Order<S>,
+{
+    /// Start counting pushes, rejections, pops and the peak frontier.
+    pub fn with_stats(self) -> Search<S, SEEN, ORDER, SearchStats> {
+        Search {
+            seen: self.seen,
+            order: self.order,
+            stats: SearchStats::default(),
+            spooky_ghost: Default::default(),
+        }
+    }
+}
+
+impl<S, SEEN, ORDER> Search<S, SEEN, ORDER, SearchStats>
+where
+    SEEN: SeenSpace<S>,
+    ORDER: Order<S>,
+{
+    /// Get a snapshot of the stats so far, including seen space occupancy.
+    pub fn stats(&self) -> SearchStats {
+        let mut stats = self.stats;
+        stats.seen_occupancy = self.seen.occupancy();
+        stats
+    }
+}
+
+impl<S, SEEN, ORDER, STATS> Search<S, SEEN, ORDER, STATS>
+where
+    SEEN: SeenSpace<S>,
+    ORDER: Order<S>,
+    STATS: StatsCollector,
     S: Copy,
 {
     pub fn dissolve(self) -> (SEEN, ORDER) {
@@ -39,6 +73,7 @@ where
     pub fn reset(&mut self) {
         self.order.reset();
         self.seen.reset();
+        self.stats.record_reset();
     }
 
     pub fn seen(&self) -> &SEEN {
@@ -52,8 +87,10 @@ where
     pub fn push(&mut self, s: S) -> bool {
         if self.seen.try_mark_seen(s) {
             self.order.push(s);
+            self.stats.record_push(true);
             true
         } else {
+            self.stats.record_push(false);
             false
         }
     }
@@ -63,6 +100,7 @@ where
         F: FnMut(&mut Self, S) -> Option<T>,
     {
         while let Some(step) = self.order.next() {
+            self.stats.record_pop();
             if let Some(res) = f(self, step) {
                 return Some(res);
             }
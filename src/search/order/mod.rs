@@ -1,4 +1,4 @@
-use crate::search::{NoSeenSpace, Search, SeenSpace};
+use crate::search::{NoSeenSpace, NoStats, Search, SeenSpace};
 
 pub mod bfs;
 pub mod dfs;
@@ -16,6 +16,7 @@ pub trait Order<S>: Sized {
         Search {
             seen,
             order: self,
+            stats: NoStats,
             spooky_ghost: Default::default(),
         }
     }
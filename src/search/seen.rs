@@ -1,7 +1,8 @@
 use super::{Cost, Key};
-use crate::grid::{Grid, GridCoordinate};
+use crate::grid::{BitGrid, Grid, GridCoordinate, GrowingGrid, SparseGrid};
 use bit_vec::BitVec;
 use num::Zero;
+use num::{FromPrimitive, ToPrimitive};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::hash_map::Entry;
 use std::hash::Hash;
@@ -16,6 +17,11 @@ pub trait SeenSpace<S> {
     /// Try to mark something as seen, returning true only if it passes
     /// it.
     fn try_mark_seen(&mut self, state: S) -> bool;
+    /// How many entries are marked, if the seen space can tell. It's only
+    /// used for stats, so it may be slow.
+    fn occupancy(&self) -> Option<usize> {
+        None
+    }
 }
 
 macro_rules! uint_bitset_space {
@@ -39,6 +45,9 @@ macro_rules! uint_bitset_space {
                     false
                 }
             }
+            fn occupancy(&self) -> Option<usize> {
+                Some(self.count_ones() as usize)
+            }
         }
     };
 }
@@ -66,8 +75,8 @@ where
     #[inline]
     fn try_mark_seen(&mut self, state: S) -> bool {
         let index = state.key();
-        if self.len() > index {
-            self.grow(self.len() - index, false);
+        if index >= self.len() {
+            self.grow(index - self.len(), false);
             self.push(true);
             true
         } else if self.get(index) == Some(false) {
@@ -77,6 +86,10 @@ where
             false
         }
     }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.iter().filter(|b| *b).count())
+    }
 }
 
 impl<S, C> SeenSpace<S> for Vec<C>
@@ -109,6 +122,10 @@ where
             panic!("seen space vec out of range")
         }
     }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.iter().filter(|c| !c.is_zero()).count())
+    }
 }
 
 impl<S, C, const N: usize> SeenSpace<S> for [C; N]
@@ -141,6 +158,10 @@ where
             panic!("seen space vec out of range")
         }
     }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.iter().filter(|c| !c.is_zero()).count())
+    }
 }
 
 impl<S, K> SeenSpace<S> for FxHashSet<K>
@@ -162,6 +183,10 @@ where
     fn try_mark_seen(&mut self, state: S) -> bool {
         self.insert(state.key())
     }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.len())
+    }
 }
 
 impl<S, K, C> SeenSpace<S> for FxHashMap<K, C>
@@ -202,6 +227,9 @@ where
             }
         }
     }
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.len())
+    }
 }
 
 pub struct ReEntrantSeenMap<K, C> {
@@ -254,6 +282,9 @@ where
             }
         }
     }
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.hash_map.len())
+    }
 }
 
 impl<C, S, T> SeenSpace<S> for Grid<C, S, T>
@@ -287,6 +318,111 @@ where
             false
         }
     }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.iter().filter(|(_, c)| !c.is_zero()).count())
+    }
+}
+
+impl<S, K, C> SeenSpace<S> for SparseGrid<K, C>
+where
+    S: Key<K> + Cost<C>,
+    K: Hash + Eq + Copy,
+    C: Ord,
+{
+    #[inline]
+    fn reset(&mut self) {
+        self.clear();
+    }
+
+    #[inline]
+    fn has_seen(&self, state: &S) -> bool {
+        match self.get(&state.key()) {
+            Some(existing_cost) => state.cost() >= *existing_cost,
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn try_mark_seen(&mut self, state: S) -> bool {
+        let (key, state_cost) = (state.key(), state.cost());
+        match self.get(&key) {
+            Some(existing_cost) if *existing_cost <= state_cost => false,
+            _ => {
+                self.set(key, state_cost);
+                true
+            }
+        }
+    }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<S, C, T> SeenSpace<S> for GrowingGrid<C, T>
+where
+    S: Key<(C, C)> + Cost<T>,
+    C: ToPrimitive + FromPrimitive,
+    T: Zero + Copy + Ord,
+{
+    fn reset(&mut self) {
+        self.clear();
+    }
+
+    fn has_seen(&self, state: &S) -> bool {
+        match self.cell(&state.key()) {
+            Some(existing_cost) => !existing_cost.is_zero() && state.cost() >= *existing_cost,
+            None => false,
+        }
+    }
+
+    fn try_mark_seen(&mut self, state: S) -> bool {
+        if let Some(existing_cost) = self.cell_mut(&state.key()) {
+            let state_cost = state.cost();
+            if existing_cost.is_zero() || state_cost < *existing_cost {
+                *existing_cost = state_cost;
+                true
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.iter().filter(|(_, c)| !c.is_zero()).count())
+    }
+}
+
+impl<S, C> SeenSpace<S> for BitGrid<C>
+where
+    S: Key<(C, C)>,
+    (C, C): GridCoordinate,
+    C: ToPrimitive + FromPrimitive,
+{
+    #[inline]
+    fn reset(&mut self) {
+        self.clear();
+    }
+
+    /// Out of bounds counts as seen, the same as the dense grid.
+    #[inline]
+    fn has_seen(&self, state: &S) -> bool {
+        let key = state.key();
+        self.bit_of(&key).is_none() || self.get(&key)
+    }
+
+    #[inline]
+    fn try_mark_seen(&mut self, state: S) -> bool {
+        let key = state.key();
+        self.bit_of(&key).is_some() && !self.set(&key, true)
+    }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.count_ones())
+    }
 }
 
 pub struct NoSeenSpace;
@@ -360,4 +496,49 @@ where
             false
         }
     }
+
+    fn occupancy(&self) -> Option<usize> {
+        Some(self.data.iter().map(|w| w.count_ones() as usize).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Pos(u8, u8);
+
+    impl Key<(u8, u8)> for Pos {
+        fn key(&self) -> (u8, u8) {
+            (self.0, self.1)
+        }
+    }
+
+    #[test]
+    fn bit_vec_grows_to_fit() {
+        let mut seen = BitVec::from_elem(3, false);
+        assert!(seen.try_mark_seen(10usize));
+        assert_eq!(seen.len(), 11);
+        assert!(!seen.has_seen(&9usize));
+        assert!(seen.has_seen(&10usize));
+        assert!(!seen.has_seen(&11usize));
+        assert!(!seen.try_mark_seen(10usize));
+
+        assert!(seen.try_mark_seen(11usize));
+        assert!(seen.has_seen(&11usize));
+        assert!(!seen.has_seen(&2usize));
+    }
+
+    #[test]
+    fn bit_grid_rejects_out_of_bounds() {
+        let mut seen = BitGrid::<u8>::new((4, 4));
+        assert!(!seen.try_mark_seen(Pos(5, 1)));
+        assert!(!seen.try_mark_seen(Pos(5, 1)));
+        assert!(seen.has_seen(&Pos(5, 1)));
+
+        assert!(!seen.has_seen(&Pos(3, 1)));
+        assert!(seen.try_mark_seen(Pos(3, 1)));
+        assert!(!seen.try_mark_seen(Pos(3, 1)));
+        assert_eq!(SeenSpace::<Pos>::occupancy(&seen), Some(1));
+    }
 }
@@ -0,0 +1,133 @@
+use std::fmt::{Display, Formatter};
+
+/// Receives events from a search. The no-op version is the default, so that
+/// the calls compile away when nobody's looking.
+pub trait StatsCollector {
+    /// A state was pushed, `accepted` is false if the seen space rejected it.
+    fn record_push(&mut self, accepted: bool);
+    /// A state was taken out of the order.
+    fn record_pop(&mut self);
+    /// The search was reset. Counters should be kept, but not the frontier.
+    fn record_reset(&mut self);
+}
+
+pub struct NoStats;
+
+impl StatsCollector for NoStats {
+    #[inline(always)]
+    fn record_push(&mut self, _: bool) {}
+
+    #[inline(always)]
+    fn record_pop(&mut self) {}
+
+    #[inline(always)]
+    fn record_reset(&mut self) {}
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct SearchStats {
+    /// States that made it past the seen space.
+    pub pushed: usize,
+    /// States rejected by the seen space.
+    pub rejected: usize,
+    /// States taken out of the order.
+    pub popped: usize,
+    /// The largest the order has been.
+    pub peak_frontier: usize,
+    /// How many entries the seen space holds, if it can tell.
+    pub seen_occupancy: Option<usize>,
+    frontier: usize,
+}
+
+impl StatsCollector for SearchStats {
+    #[inline]
+    fn record_push(&mut self, accepted: bool) {
+        if accepted {
+            self.pushed += 1;
+            self.frontier += 1;
+            if self.frontier > self.peak_frontier {
+                self.peak_frontier = self.frontier;
+            }
+        } else {
+            self.rejected += 1;
+        }
+    }
+
+    #[inline]
+    fn record_pop(&mut self) {
+        self.popped += 1;
+        self.frontier = self.frontier.saturating_sub(1);
+    }
+
+    #[inline]
+    fn record_reset(&mut self) {
+        self.frontier = 0;
+    }
+}
+
+impl Display for SearchStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "pushed: {}, rejected: {}, popped: {}, peak frontier: {}",
+            self.pushed, self.rejected, self.popped, self.peak_frontier
+        )?;
+        if let Some(occupancy) = self.seen_occupancy {
+            write!(f, ", seen: {}", occupancy)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::{bfs, dfs, OnlyKey, Order};
+    use rustc_hash::FxHashSet;
+
+    #[test]
+    fn counts_line_walk() {
+        let mut search = bfs()
+            .with_seen_space(FxHashSet::<u32>::default())
+            .with_stats();
+        search.push(OnlyKey(0u32));
+        let res = search.find(|search, OnlyKey(n)| {
+            if n == 10 {
+                return Some(n);
+            }
+
+            search.push(OnlyKey(n + 1));
+            if n > 0 {
+                search.push(OnlyKey(n - 1));
+            }
+
+            None
+        });
+
+        assert_eq!(res, Some(10));
+        let stats = search.stats();
+        assert_eq!(stats.pushed, 11);
+        assert_eq!(stats.rejected, 9);
+        assert_eq!(stats.popped, 11);
+        assert_eq!(stats.peak_frontier, 1);
+        assert_eq!(stats.seen_occupancy, Some(11));
+    }
+
+    #[test]
+    fn tracks_peak_frontier() {
+        let mut search = dfs().without_seen_space().with_stats();
+        for i in 0..5u32 {
+            search.push(OnlyKey(i));
+        }
+        search.find(|_, _| None::<()>);
+
+        let stats = search.stats();
+        assert_eq!(stats.peak_frontier, 5);
+        assert_eq!(stats.popped, 5);
+        assert_eq!(stats.seen_occupancy, None);
+        assert_eq!(
+            format!("{}", stats),
+            "pushed: 5, rejected: 0, popped: 5, peak frontier: 5"
+        );
+    }
+}
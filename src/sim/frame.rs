@@ -0,0 +1,100 @@
+use crate::utils::UnionFind;
+use rustc_hash::FxHashMap;
+
+/// The positions of every particle at one point in time.
+#[derive(Debug, Clone, Default)]
+pub struct Frame {
+    pub width: i64,
+    pub height: i64,
+    pub xs: Vec<i64>,
+    pub ys: Vec<i64>,
+}
+
+impl Frame {
+    pub fn new(width: i64, height: i64) -> Self {
+        Self {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.xs.iter().copied().zip(self.ys.iter().copied())
+    }
+
+    /// The number of particles in each quadrant, from the top left and then row by row.
+    /// The ones on the middle row or column don't count.
+    pub fn quadrants(&self) -> [usize; 4] {
+        let (mx, my) = (self.width / 2, self.height / 2);
+        let mut res = [0; 4];
+        for (x, y) in self.positions() {
+            if (self.width % 2 == 1 && x == mx) || (self.height % 2 == 1 && y == my) {
+                continue;
+            }
+
+            res[(x >= mx + self.width % 2) as usize + 2 * (y >= my + self.height % 2) as usize] +=
+                1;
+        }
+
+        res
+    }
+
+    /// The Shannon entropy, in bits, of how the particles are spread out over `block`
+    /// by `block` squares. It drops when they clump up.
+    pub fn entropy(&self, block: i64) -> f64 {
+        let mut counts = FxHashMap::default();
+        for (x, y) in self.positions() {
+            *counts.entry((x / block, y / block)).or_insert(0u32) += 1;
+        }
+
+        let n = self.xs.len() as f64;
+        counts
+            .values()
+            .map(|c| {
+                let p = *c as f64 / n;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// The size, in cells, of the largest group of occupied cells that touch on a side.
+    /// It doesn't wrap around the edges.
+    pub fn largest_cluster(&self) -> usize {
+        let mut cells = FxHashMap::default();
+        for pos in self.positions() {
+            let next = cells.len();
+            cells.entry(pos).or_insert(next);
+        }
+
+        let mut uf = UnionFind::new(cells.len());
+        for ((x, y), i) in cells.iter() {
+            for neighbor in [(x + 1, *y), (*x, y + 1)] {
+                if let Some(j) = cells.get(&neighbor) {
+                    uf.union(*i, *j);
+                }
+            }
+        }
+
+        (0..uf.len()).map(|i| uf.group_size(i)).max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detectors() {
+        let mut frame = Frame::new(7, 5);
+        frame.xs = vec![0, 1, 1, 3, 6, 5, 6];
+        frame.ys = vec![0, 0, 1, 4, 4, 2, 3];
+        assert_eq!(frame.quadrants(), [3, 0, 0, 2]);
+        assert_eq!(frame.largest_cluster(), 3);
+
+        let spread = frame.entropy(1);
+        frame.xs = vec![2; 7];
+        assert!(frame.entropy(1) < spread);
+        assert_eq!(Frame::new(3, 3).entropy(1), 0.0);
+    }
+}
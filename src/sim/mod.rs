@@ -0,0 +1,202 @@
+mod frame;
+
+use crate::utils::{crt_generalized, lcm};
+use std::ops::Range;
+
+pub use frame::Frame;
+
+/// Particles moving at a constant velocity on a board that wraps around at the edges.
+/// The coordinates are kept in separate arrays so the per-axis loops vectorize.
+#[derive(Debug, Clone, Default)]
+pub struct Particles {
+    pub width: i64,
+    pub height: i64,
+    pub px: Vec<i64>,
+    pub py: Vec<i64>,
+    pub vx: Vec<i64>,
+    pub vy: Vec<i64>,
+}
+
+impl Particles {
+    pub fn new(width: i64, height: i64) -> Self {
+        Self {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    /// Add a particle. Positions and velocities can be anything, they get wrapped.
+    pub fn push(&mut self, p: (i64, i64), v: (i64, i64)) {
+        self.px.push(p.0.rem_euclid(self.width));
+        self.py.push(p.1.rem_euclid(self.height));
+        self.vx.push(v.0.rem_euclid(self.width));
+        self.vy.push(v.1.rem_euclid(self.height));
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.px.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.px.is_empty()
+    }
+
+    /// The time it takes for every particle to be back where it started. Each axis
+    /// repeats on its own after `width` and `height` steps.
+    #[inline]
+    pub fn period(&self) -> i64 {
+        lcm(self.width, self.height)
+    }
+
+    /// Where the particle is at time `t`, which can be negative.
+    pub fn position_at(&self, i: usize, t: i64) -> (i64, i64) {
+        (
+            Self::wrap(self.px[i], self.vx[i], t, self.width),
+            Self::wrap(self.py[i], self.vy[i], t, self.height),
+        )
+    }
+
+    /// The x coordinates at time `t`, written into `out`.
+    pub fn xs_at(&self, t: i64, out: &mut Vec<i64>) {
+        Self::axis_at(&self.px, &self.vx, t, self.width, out)
+    }
+
+    /// The y coordinates at time `t`, written into `out`.
+    pub fn ys_at(&self, t: i64, out: &mut Vec<i64>) {
+        Self::axis_at(&self.py, &self.vy, t, self.height, out)
+    }
+
+    pub fn frame(&self, t: i64) -> Frame {
+        let mut frame = Frame::new(self.width, self.height);
+        self.frame_into(t, &mut frame);
+        frame
+    }
+
+    /// Like `frame`, but reusing the buffers of an old one.
+    pub fn frame_into(&self, t: i64, frame: &mut Frame) {
+        frame.width = self.width;
+        frame.height = self.height;
+        self.xs_at(t, &mut frame.xs);
+        self.ys_at(t, &mut frame.ys);
+    }
+
+    /// The times where the x and y coordinates are the least spread out. Each is searched
+    /// within its own axis' period, so this is a lot cheaper than looking at whole frames.
+    pub fn variance_minima(&self) -> (i64, i64) {
+        let mut buf = Vec::with_capacity(self.len());
+        let tx = Self::axis_minimum(0..self.width, |t| {
+            self.xs_at(t, &mut buf);
+            variance(&buf)
+        });
+        let ty = Self::axis_minimum(0..self.height, |t| {
+            self.ys_at(t, &mut buf);
+            variance(&buf)
+        });
+
+        (tx, ty)
+    }
+
+    /// The first time where both axes are at their variance minimum, found by combining
+    /// them with the CRT. That's when the particles are bunched together the most, if they
+    /// ever are.
+    pub fn variance_structure(&self) -> Option<i64> {
+        let (tx, ty) = self.variance_minima();
+        crt_generalized(&[(tx, self.width), (ty, self.height)]).map(|(t, _)| t)
+    }
+
+    /// Look at every frame in `times` and return the one with the lowest score. This is for
+    /// detectors that need whole frames, like `Frame::entropy`.
+    pub fn scan<F>(&self, times: Range<i64>, mut score: F) -> Option<(i64, f64)>
+    where
+        F: FnMut(&Frame) -> f64,
+    {
+        let mut frame = Frame::new(self.width, self.height);
+        let mut best: Option<(i64, f64)> = None;
+        for t in times {
+            self.frame_into(t, &mut frame);
+            let s = score(&frame);
+            if best.is_none_or(|(_, b)| s < b) {
+                best = Some((t, s));
+            }
+        }
+
+        best
+    }
+
+    #[inline]
+    fn wrap(p: i64, v: i64, t: i64, size: i64) -> i64 {
+        (p + v * t.rem_euclid(size)).rem_euclid(size)
+    }
+
+    fn axis_at(p: &[i64], v: &[i64], t: i64, size: i64, out: &mut Vec<i64>) {
+        let t = t.rem_euclid(size);
+        out.clear();
+        out.extend(p.iter().zip(v).map(|(p, v)| (p + v * t) % size));
+    }
+
+    fn axis_minimum<F: FnMut(i64) -> f64>(times: Range<i64>, mut score: F) -> i64 {
+        times
+            .map(|t| (t, score(t)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(t, _)| t)
+            .unwrap_or(0)
+    }
+}
+
+/// The population variance of the values.
+pub fn variance(values: &[i64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let n = values.len() as f64;
+    let sum: i64 = values.iter().sum();
+    let sum_sq: i64 = values.iter().map(|v| v * v).sum();
+    let mean = sum as f64 / n;
+
+    sum_sq as f64 / n - mean * mean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3 block in the middle at `t`, that's scattered at any other time.
+    fn block_at(t: i64) -> Particles {
+        let mut particles = Particles::new(11, 13);
+        for i in 0..9 {
+            let target = (4 + i % 3, 5 + i / 3);
+            let v = (i + 1, 2 * i + 1);
+            particles.push((target.0 - v.0 * t, target.1 - v.1 * t), v);
+        }
+
+        particles
+    }
+
+    #[test]
+    fn closed_form() {
+        let mut particles = Particles::new(11, 7);
+        particles.push((2, 4), (2, -3));
+        assert_eq!(particles.position_at(0, 1), (4, 1));
+        assert_eq!(particles.position_at(0, 5), (1, 3));
+        assert_eq!(particles.position_at(0, -1), (0, 0));
+        assert_eq!(particles.position_at(0, 77 + 5), (1, 3));
+
+        let frame = particles.frame(5);
+        assert_eq!((frame.xs[0], frame.ys[0]), (1, 3));
+    }
+
+    #[test]
+    fn finds_block_with_variance() {
+        let particles = block_at(50);
+        assert_eq!(particles.period(), 143);
+        assert_eq!(particles.variance_minima(), (50 % 11, 50 % 13));
+        assert_eq!(particles.variance_structure(), Some(50));
+
+        let frame = particles.frame(50);
+        assert_eq!(frame.largest_cluster(), 9);
+    }
+}
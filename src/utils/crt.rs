@@ -1,30 +1,57 @@
-pub fn crt(values: &[(i64, i64)]) -> i64 {
-    let prod = values.iter().map(|(_, m)| *m as i64).product::<i64>();
+use super::number::mod_inv;
+use num::{BigInt, Integer, Signed};
 
-    let sum = values
+/// Find the smallest non-negative `x` where `x % m == r` for every `(r, m)`. The moduli
+/// don't need to be coprime, but it panics if the congruences contradict each other, or
+/// if the answer doesn't fit in an `i64`.
+pub fn crt(values: &[(i64, i64)]) -> i64 {
+    let values: Vec<(i128, i128)> = values
         .iter()
-        .map(|(r, m)| r * mod_inv(prod / *m, *m).unwrap() * (prod / *m))
-        .sum::<i64>();
+        .map(|(r, m)| (*r as i128, *m as i128))
+        .collect();
+    let (r, _) = crt_generalized(&values).expect("inconsistent congruences");
 
-    sum % prod
+    i64::try_from(r).expect("crt result overflows i64")
 }
 
-fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
-    if a == 0 {
-        (b, 0, 1)
-    } else {
-        let (g, x, y) = egcd(b % a, a);
-        (g, y - (b / a) * x, x)
+/// Merge the congruences into one `(r, m)` where `m` is the lcm of the moduli, or
+/// `None` if they can't all hold or a modulus isn't positive. The intermediate products
+/// are at most about `m²`, so pick a `T` that fits that.
+pub fn crt_generalized<T>(values: &[(T, T)]) -> Option<(T, T)>
+where
+    T: Integer + Signed + Clone,
+{
+    let mut res = (T::zero(), T::one());
+    for (r, m) in values {
+        if !m.is_positive() {
+            return None;
+        }
+
+        let (r1, m1) = res;
+        let r2 = r.mod_floor(m);
+        let g = m1.gcd(m);
+        let diff = r2 - r1.clone();
+        if !diff.is_multiple_of(&g) {
+            return None;
+        }
+
+        let m2 = m.clone() / g.clone();
+        let k = (diff / g * mod_inv(m1.clone() / m1.gcd(m), m2.clone())?).mod_floor(&m2);
+        let m = m1.clone() * m2;
+        res = ((r1 + m1 * k).mod_floor(&m), m);
     }
+
+    Some(res)
 }
 
-fn mod_inv(x: i64, n: i64) -> Option<i64> {
-    let (g, x, _) = egcd(x, n);
-    if g == 1 {
-        Some((x % n + n) % n)
-    } else {
-        None
-    }
+/// `crt_generalized` on `BigInt`s, for when the lcm doesn't fit in an `i64`.
+pub fn crt_big(values: &[(i64, i64)]) -> Option<(BigInt, BigInt)> {
+    let values: Vec<(BigInt, BigInt)> = values
+        .iter()
+        .map(|(r, m)| (BigInt::from(*r), BigInt::from(*m)))
+        .collect();
+
+    crt_generalized(&values)
 }
 
 #[cfg(test)]
@@ -68,4 +95,33 @@ mod tests {
             23
         );
     }
+
+    #[test]
+    fn generalized_merges_non_coprime() {
+        assert_eq!(crt_generalized(&[(2i64, 6), (8, 10)]), Some((8, 30)));
+        assert_eq!(crt_generalized(&[(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt_generalized(&[(1i64, 4), (2, 0)]), None);
+        assert_eq!(crt_generalized(&[(1i64, -3)]), None);
+        assert_eq!(crt(&[(3, 4), (-1, 6)]), 11);
+        assert_eq!(
+            crt_generalized(&[(7i64, 7), (12, 13), (55, 59), (25, 31), (12, 19)]),
+            Some((1068781, 7 * 13 * 59 * 31 * 19))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "crt result overflows i64")]
+    fn overflow_panics() {
+        crt(&[(1, 1_000_000_007), (2, 998_244_353), (3, 1_000_000_009)]);
+    }
+
+    #[test]
+    fn big_moduli() {
+        let primes = [1_000_000_007i64, 998_244_353, 1_000_000_009];
+        let x: i128 = 123_456_789_012_345_678_901_234;
+        let values = primes.map(|p| ((x % p as i128) as i64, p));
+        let (r, m) = crt_big(&values).unwrap();
+        assert_eq!(r, BigInt::from(x));
+        assert_eq!(m, primes.iter().map(|p| BigInt::from(*p)).product());
+    }
 }
@@ -0,0 +1,173 @@
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+/// Find the cycle of the sequence `start, step(start), step(step(start)), ...` with
+/// Floyd's tortoise and hare, returning `(mu, lambda)`: the index of the first state in
+/// the cycle, and its length. It only needs `Eq`, but it will never return if the
+/// sequence doesn't cycle.
+pub fn floyd<S, F>(start: S, mut step: F) -> (usize, usize)
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut mu = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// Same as `floyd`, but with Brent's algorithm, which calls `step` fewer times.
+pub fn brent<S, F>(start: S, mut step: F) -> (usize, usize)
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// Find the cycle by remembering every state, returning `(mu, lambda)` like `floyd`. It
+/// calls `step` exactly `mu + lambda` times, so it's the one to use when stepping is
+/// expensive. It gives up with `None` after `max_steps`.
+pub fn find_cycle<S, F>(start: S, mut step: F, max_steps: usize) -> Option<(usize, usize)>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = FxHashMap::default();
+    let mut current = start;
+    for i in 0..=max_steps {
+        if let Some(mu) = seen.insert(current.clone(), i) {
+            return Some((mu, i - mu));
+        }
+        current = step(&current);
+    }
+
+    None
+}
+
+/// The state after `n` steps. It keeps the history, so once a state repeats it jumps
+/// straight to the answer instead of stepping through the rest of the cycles.
+pub fn fast_forward<S, F>(start: S, mut step: F, n: usize) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = FxHashMap::default();
+    let mut history = Vec::new();
+    let mut current = start;
+    for i in 0..n {
+        if let Some(mu) = seen.insert(current.clone(), i) {
+            let lambda = i - mu;
+            return history.swap_remove(mu + (n - mu) % lambda);
+        }
+
+        let next = step(&current);
+        history.push(current);
+        current = next;
+    }
+
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lcg(x: &u32) -> u32 {
+        (x * 13 + 7) % 100 + if *x < 10 { 100 } else { 0 }
+    }
+
+    fn naive(start: u32) -> (usize, usize) {
+        let mut history = vec![start];
+        loop {
+            let next = lcg(history.last().unwrap());
+            if let Some(mu) = history.iter().position(|x| *x == next) {
+                return (mu, history.len() - mu);
+            }
+            history.push(next);
+        }
+    }
+
+    #[test]
+    fn detectors_agree() {
+        for start in [5, 42, 99, 150] {
+            let expected = naive(start);
+            assert_eq!(floyd(start, lcg), expected);
+            assert_eq!(brent(start, lcg), expected);
+            assert_eq!(find_cycle(start, lcg, 1000), Some(expected));
+        }
+
+        assert_eq!(find_cycle(0u64, |x| x + 1, 1000), None);
+        assert_eq!(find_cycle(0u8, |x| (x + 1) % 3, 1000), Some((0, 3)));
+    }
+
+    #[test]
+    fn fast_forward_matches_stepping() {
+        let mut state = 5u32;
+        for n in 0..300 {
+            assert_eq!(fast_forward(5, lcg, n), state);
+            state = lcg(&state);
+        }
+
+        let spin = |s: &Vec<u8>| {
+            let mut s = s.clone();
+            s.rotate_left(3);
+            s.swap(0, 4);
+            s
+        };
+        let start = b"abcdefg".to_vec();
+        let mut expected = start.clone();
+        let (mu, lambda) = find_cycle(start.clone(), spin, 10000).unwrap();
+        for _ in 0..mu + (1_000_000_000 - mu) % lambda {
+            expected = spin(&expected);
+        }
+        assert_eq!(fast_forward(start, spin, 1_000_000_000), expected);
+    }
+}
@@ -1,6 +1,7 @@
+use crate::search::Key;
 use num::traits::WrappingSub;
-use num::One;
-use std::ops::Add;
+use num::{One, Signed};
+use std::ops::{Add, BitAnd, BitOr, Not};
 
 #[derive(Debug, PartialEq, Copy, Clone, Hash, Eq)]
 pub enum CardinalDirection {
@@ -72,4 +73,368 @@ impl CardinalDirection {
             CardinalDirection::South => CardinalDirection::North,
         }
     }
+
+    /// Index in clockwise order from north, the same order as `OrdinalDirection` skipping
+    /// the diagonals.
+    pub fn index(&self) -> usize {
+        match self {
+            CardinalDirection::North => 0,
+            CardinalDirection::East => 1,
+            CardinalDirection::South => 2,
+            CardinalDirection::West => 3,
+        }
+    }
+
+    pub fn from_index(index: usize) -> CardinalDirection {
+        match index % 4 {
+            0 => CardinalDirection::North,
+            1 => CardinalDirection::East,
+            2 => CardinalDirection::South,
+            _ => CardinalDirection::West,
+        }
+    }
+
+    /// Turn `n` quarter-turns with the clock, or against it if `n` is negative.
+    pub fn rotate(&self, n: i32) -> CardinalDirection {
+        Self::from_index((self.index() as i32 + n).rem_euclid(4) as usize)
+    }
+
+    /// The step in this direction, with y growing downwards.
+    pub fn delta<T: Signed>(&self) -> (T, T) {
+        OrdinalDirection::from(*self).delta()
+    }
+
+    /// Parse an arrow (`^>v<`), compass letter (`NESW`) or `UDLR`.
+    pub fn from_char(c: u8) -> Option<CardinalDirection> {
+        match c {
+            b'^' | b'N' | b'U' => Some(CardinalDirection::North),
+            b'>' | b'E' | b'R' => Some(CardinalDirection::East),
+            b'v' | b'S' | b'D' => Some(CardinalDirection::South),
+            b'<' | b'W' | b'L' => Some(CardinalDirection::West),
+            _ => None,
+        }
+    }
+}
+
+impl Key<usize> for CardinalDirection {
+    fn key(&self) -> usize {
+        self.index()
+    }
+}
+
+/// The eight directions, in clockwise order from north.
+#[derive(Debug, PartialEq, Copy, Clone, Hash, Eq, Ord, PartialOrd)]
+pub enum OrdinalDirection {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl OrdinalDirection {
+    pub const ALL: [OrdinalDirection; 8] = [
+        OrdinalDirection::North,
+        OrdinalDirection::NorthEast,
+        OrdinalDirection::East,
+        OrdinalDirection::SouthEast,
+        OrdinalDirection::South,
+        OrdinalDirection::SouthWest,
+        OrdinalDirection::West,
+        OrdinalDirection::NorthWest,
+    ];
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    #[inline]
+    pub fn from_index(index: usize) -> OrdinalDirection {
+        Self::ALL[index % 8]
+    }
+
+    /// Turn `n` eighth-turns with the clock, or against it if `n` is negative.
+    #[inline]
+    pub fn rotate(&self, n: i32) -> OrdinalDirection {
+        Self::from_index((self.index() as i32 + n).rem_euclid(8) as usize)
+    }
+
+    #[inline]
+    pub fn opposite(&self) -> OrdinalDirection {
+        self.rotate(4)
+    }
+
+    #[inline]
+    pub fn is_diagonal(&self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    pub fn to_cardinal(&self) -> Option<CardinalDirection> {
+        if self.is_diagonal() {
+            None
+        } else {
+            Some(CardinalDirection::from_index(self.index() / 2))
+        }
+    }
+
+    /// The step in this direction, with y growing downwards.
+    pub fn delta<T: Signed>(&self) -> (T, T) {
+        let (dx, dy) = match self {
+            OrdinalDirection::North => (0, -1),
+            OrdinalDirection::NorthEast => (1, -1),
+            OrdinalDirection::East => (1, 0),
+            OrdinalDirection::SouthEast => (1, 1),
+            OrdinalDirection::South => (0, 1),
+            OrdinalDirection::SouthWest => (-1, 1),
+            OrdinalDirection::West => (-1, 0),
+            OrdinalDirection::NorthWest => (-1, -1),
+        };
+
+        let unit = |d: i8| match d {
+            1 => T::one(),
+            -1 => -T::one(),
+            _ => T::zero(),
+        };
+
+        (unit(dx), unit(dy))
+    }
+
+    /// Get the next position in this direction. Like `CardinalDirection::next_pos`, it
+    /// wraps around instead of going below zero.
+    pub fn next_pos<T>(&self, pos: &(T, T)) -> (T, T)
+    where
+        T: WrappingSub + Copy + Add<T, Output = T> + One,
+    {
+        let (dx, dy) = self.delta::<i8>();
+        let step = |v: T, d: i8| match d {
+            1 => v + T::one(),
+            -1 => v.wrapping_sub(&T::one()),
+            _ => v,
+        };
+
+        (step(pos.0, dx), step(pos.1, dy))
+    }
+
+    /// Parse an arrow (`^>v<`), compass letter (`NESW`) or `UDLR`. Only gives cardinals.
+    pub fn from_char(c: u8) -> Option<OrdinalDirection> {
+        CardinalDirection::from_char(c).map(OrdinalDirection::from)
+    }
+}
+
+impl From<CardinalDirection> for OrdinalDirection {
+    fn from(dir: CardinalDirection) -> Self {
+        Self::from_index(dir.index() * 2)
+    }
+}
+
+impl Key<usize> for OrdinalDirection {
+    fn key(&self) -> usize {
+        self.index()
+    }
+}
+
+/// A set of directions packed into the bits of a byte, one per `OrdinalDirection::index`.
+#[derive(Debug, Default, PartialEq, Copy, Clone, Hash, Eq)]
+pub struct DirectionSet(u8);
+
+impl DirectionSet {
+    pub const EMPTY: DirectionSet = DirectionSet(0);
+    pub const ALL: DirectionSet = DirectionSet(0xff);
+    pub const CARDINAL: DirectionSet = DirectionSet(0b01010101);
+    pub const DIAGONAL: DirectionSet = DirectionSet(0b10101010);
+
+    #[inline]
+    pub fn from_bits(bits: u8) -> DirectionSet {
+        DirectionSet(bits)
+    }
+
+    #[inline]
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    #[inline]
+    pub fn contains(&self, dir: impl Into<OrdinalDirection>) -> bool {
+        self.0 & (1 << dir.into().index()) != 0
+    }
+
+    /// Add the direction, returning true if it wasn't there already.
+    #[inline]
+    pub fn insert(&mut self, dir: impl Into<OrdinalDirection>) -> bool {
+        let mask = 1 << dir.into().index();
+        let was_new = self.0 & mask == 0;
+        self.0 |= mask;
+        was_new
+    }
+
+    #[inline]
+    pub fn remove(&mut self, dir: impl Into<OrdinalDirection>) -> bool {
+        let mask = 1 << dir.into().index();
+        let was_there = self.0 & mask != 0;
+        self.0 &= !mask;
+        was_there
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Rotate every direction in the set `n` eighth-turns with the clock.
+    #[inline]
+    pub fn rotate(&self, n: i32) -> DirectionSet {
+        DirectionSet(self.0.rotate_left(n.rem_euclid(8) as u32))
+    }
+
+    #[inline]
+    pub fn opposite(&self) -> DirectionSet {
+        self.rotate(4)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = OrdinalDirection> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+
+            let index = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(OrdinalDirection::from_index(index))
+        })
+    }
+
+    /// Parse every direction character in the input, skipping anything else.
+    pub fn parse(input: &[u8]) -> DirectionSet {
+        input
+            .iter()
+            .filter_map(|c| OrdinalDirection::from_char(*c))
+            .collect()
+    }
+}
+
+impl<D: Into<OrdinalDirection>> FromIterator<D> for DirectionSet {
+    fn from_iter<I: IntoIterator<Item = D>>(iter: I) -> Self {
+        let mut set = DirectionSet::EMPTY;
+        for dir in iter {
+            set.insert(dir);
+        }
+
+        set
+    }
+}
+
+impl BitOr for DirectionSet {
+    type Output = DirectionSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        DirectionSet(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for DirectionSet {
+    type Output = DirectionSet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        DirectionSet(self.0 & rhs.0)
+    }
+}
+
+impl Not for DirectionSet {
+    type Output = DirectionSet;
+
+    fn not(self) -> Self::Output {
+        DirectionSet(!self.0)
+    }
+}
+
+impl Key<usize> for DirectionSet {
+    fn key(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_and_opposites() {
+        assert_eq!(CardinalDirection::North.rotate(1), CardinalDirection::East);
+        assert_eq!(CardinalDirection::North.rotate(-1), CardinalDirection::West);
+        assert_eq!(CardinalDirection::South.rotate(6), CardinalDirection::North);
+        for dir in CardinalDirection::NWES {
+            assert_eq!(dir.rotate(1), dir.turn_clockwise());
+            assert_eq!(dir.rotate(-1), dir.turn_anticlockwise());
+            assert_eq!(dir.delta::<i32>(), OrdinalDirection::from(dir).delta());
+        }
+
+        let ne = OrdinalDirection::NorthEast;
+        assert_eq!(ne.rotate(3), OrdinalDirection::South);
+        assert_eq!(ne.rotate(-11), OrdinalDirection::West);
+        assert_eq!(ne.opposite(), OrdinalDirection::SouthWest);
+        assert_eq!(ne.delta::<i64>(), (1, -1));
+        assert_eq!(ne.next_pos(&(0u8, 0u8)), (1, 255));
+        assert_eq!(ne.to_cardinal(), None);
+        assert_eq!(ne.rotate(1).to_cardinal(), Some(CardinalDirection::East));
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(
+            b"^>v<".map(CardinalDirection::from_char),
+            b"NESW".map(CardinalDirection::from_char),
+        );
+        assert_eq!(
+            b"URDL".map(CardinalDirection::from_char),
+            [
+                Some(CardinalDirection::North),
+                Some(CardinalDirection::East),
+                Some(CardinalDirection::South),
+                Some(CardinalDirection::West),
+            ]
+        );
+        assert_eq!(OrdinalDirection::from_char(b'x'), None);
+    }
+
+    #[test]
+    fn direction_set() {
+        let mut set = DirectionSet::parse(b"^<");
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(CardinalDirection::West));
+        assert!(set.insert(OrdinalDirection::SouthEast));
+        assert!(!set.insert(OrdinalDirection::SouthEast));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![
+                OrdinalDirection::North,
+                OrdinalDirection::SouthEast,
+                OrdinalDirection::West
+            ]
+        );
+        assert_eq!(
+            set.opposite(),
+            [
+                OrdinalDirection::South,
+                OrdinalDirection::NorthWest,
+                OrdinalDirection::East
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert!(set.remove(OrdinalDirection::SouthEast));
+        assert_eq!(set & DirectionSet::DIAGONAL, DirectionSet::EMPTY);
+        assert_eq!(set | !set, DirectionSet::ALL);
+        assert_eq!(DirectionSet::CARDINAL.rotate(1), DirectionSet::DIAGONAL);
+        assert_eq!(Key::<usize>::key(&DirectionSet::CARDINAL), 0b01010101);
+    }
 }
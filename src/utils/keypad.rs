@@ -0,0 +1,213 @@
+use rustc_hash::FxHashMap;
+
+const MOVES: [(u8, (i32, i32)); 4] = [
+    (b'^', (0, -1)),
+    (b'>', (1, 0)),
+    (b'v', (0, 1)),
+    (b'<', (-1, 0)),
+];
+
+/// A keypad with an arm hovering over it, moved with `^>v<` and pressed with `A`. Every
+/// shortest path between two keys that stays off the gaps is worked out up front.
+#[derive(Debug, Clone)]
+pub struct Keypad {
+    positions: FxHashMap<u8, (i32, i32)>,
+    paths: FxHashMap<(u8, u8), Vec<Vec<u8>>>,
+}
+
+impl Keypad {
+    pub const NUMERIC: &'static str = "789\n456\n123\n 0A";
+    pub const DIRECTIONAL: &'static str = " ^A\n<v>";
+
+    /// One row per line, with spaces or `.` for gaps.
+    pub fn parse(layout: &str) -> Keypad {
+        let mut positions = FxHashMap::default();
+        for (y, line) in layout.lines().enumerate() {
+            for (x, key) in line.bytes().enumerate() {
+                if key != b' ' && key != b'.' {
+                    positions.insert(key, (x as i32, y as i32));
+                }
+            }
+        }
+
+        let mut keypad = Keypad {
+            positions,
+            paths: FxHashMap::default(),
+        };
+        let keys: Vec<u8> = keypad.keys().collect();
+        for a in keys.iter() {
+            for b in keys.iter() {
+                let paths = keypad.find_paths(*a, *b);
+                keypad.paths.insert((*a, *b), paths);
+            }
+        }
+
+        keypad
+    }
+
+    pub fn numeric() -> Keypad {
+        Self::parse(Self::NUMERIC)
+    }
+
+    pub fn directional() -> Keypad {
+        Self::parse(Self::DIRECTIONAL)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = u8> + '_ {
+        self.positions.keys().copied()
+    }
+
+    #[inline]
+    pub fn position(&self, key: u8) -> Option<(i32, i32)> {
+        self.positions.get(&key).copied()
+    }
+
+    pub fn key_at(&self, pos: (i32, i32)) -> Option<u8> {
+        self.positions
+            .iter()
+            .find(|(_, p)| **p == pos)
+            .map(|(key, _)| *key)
+    }
+
+    /// The shortest ways of getting from `a` to `b` and pressing it, each ending with `A`.
+    /// It's empty if either key is missing, or if `b` can't be reached.
+    pub fn paths(&self, a: u8, b: u8) -> &[Vec<u8>] {
+        self.paths.get(&(a, b)).map(|p| p.as_slice()).unwrap_or(&[])
+    }
+
+    fn find_paths(&self, a: u8, b: u8) -> Vec<Vec<u8>> {
+        // Distances to b, so the paths can be walked from a by always getting closer.
+        let mut dist = FxHashMap::default();
+        let mut queue = std::collections::VecDeque::from([(self.positions[&b], 0u32)]);
+        dist.insert(self.positions[&b], 0);
+        while let Some(((x, y), d)) = queue.pop_front() {
+            for (_, (dx, dy)) in MOVES {
+                let next = (x + dx, y + dy);
+                if self.key_at(next).is_some() && !dist.contains_key(&next) {
+                    dist.insert(next, d + 1);
+                    queue.push_back((next, d + 1));
+                }
+            }
+        }
+
+        let mut res = Vec::new();
+        let mut stack = vec![(self.positions[&a], Vec::new())];
+        while let Some((pos, mut path)) = stack.pop() {
+            let Some(d) = dist.get(&pos).copied() else {
+                continue;
+            };
+            if d == 0 {
+                path.push(b'A');
+                res.push(path);
+                continue;
+            }
+
+            for (button, (dx, dy)) in MOVES {
+                let next = (pos.0 + dx, pos.1 + dy);
+                if dist.get(&next) == Some(&(d - 1)) {
+                    let mut path = path.clone();
+                    path.push(button);
+                    stack.push((next, path));
+                }
+            }
+        }
+
+        res.sort_unstable();
+        res
+    }
+}
+
+/// A code typed on the `door` keypad by a robot, whose arm is worked from a directional
+/// keypad by another robot, and so on `layers` times until there's you at the last
+/// directional keypad. Costs are cached per layer and key pair, so keep the chain
+/// around between codes.
+pub struct RobotChain {
+    door: Keypad,
+    arrows: Keypad,
+    layers: usize,
+    cache: FxHashMap<(usize, u8, u8), u64>,
+}
+
+impl RobotChain {
+    pub fn new(door: Keypad, arrows: Keypad, layers: usize) -> Self {
+        Self {
+            door,
+            arrows,
+            layers,
+            cache: FxHashMap::default(),
+        }
+    }
+
+    /// The fewest presses you need to make for the code to be typed on the door, or `None`
+    /// if it has a key that's missing or can't be reached.
+    pub fn cost(&mut self, code: &[u8]) -> Option<u64> {
+        self.cost_at(0, code)
+    }
+
+    fn cost_at(&mut self, level: usize, code: &[u8]) -> Option<u64> {
+        if level > self.layers {
+            return Some(code.len() as u64);
+        }
+
+        let mut total = 0;
+        let mut current = b'A';
+        for next in code.iter().copied() {
+            total += if let Some(cost) = self.cache.get(&(level, current, next)) {
+                *cost
+            } else {
+                let keypad = if level == 0 { &self.door } else { &self.arrows };
+                let paths = keypad.paths(current, next).to_vec();
+                let cost = paths
+                    .iter()
+                    .filter_map(|path| self.cost_at(level + 1, path))
+                    .min()?;
+
+                self.cache.insert((level, current, next), cost);
+                cost
+            };
+
+            current = next;
+        }
+
+        Some(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn avoids_gaps() {
+        let keypad = Keypad::numeric();
+        assert_eq!(
+            keypad.paths(b'A', b'1'),
+            &[b"<^<A".to_vec(), b"^<<A".to_vec()]
+        );
+        assert_eq!(keypad.paths(b'7', b'0').len(), 3);
+        assert!(!keypad.paths(b'7', b'0').contains(&b"vvv>A".to_vec()));
+        assert_eq!(keypad.paths(b'2', b'9').len(), 3);
+        assert_eq!(keypad.position(b'0'), Some((1, 3)));
+        assert_eq!(keypad.key_at((0, 3)), None);
+
+        let odd = Keypad::parse("1.2\n345");
+        assert_eq!(odd.paths(b'1', b'2'), &[b"v>>^A".to_vec()]);
+        assert_eq!(odd.paths(b'1', b'9'), &[] as &[Vec<u8>]);
+    }
+
+    #[test]
+    fn robot_chains() {
+        let mut chain = RobotChain::new(Keypad::numeric(), Keypad::directional(), 0);
+        assert_eq!(chain.cost(b"029A"), Some(12));
+
+        let mut chain = RobotChain::new(Keypad::numeric(), Keypad::directional(), 2);
+        assert_eq!(chain.cost(b"029A"), Some(68));
+        assert_eq!(chain.cost(b"179A"), Some(68));
+        assert_eq!(chain.cost(b"379A"), Some(64));
+        assert_eq!(chain.cost(b"02BA"), None);
+
+        let mut chain = RobotChain::new(Keypad::parse("1.2\nA.3"), Keypad::directional(), 1);
+        assert_eq!(chain.cost(b"1A"), Some(10));
+        assert_eq!(chain.cost(b"2A"), None);
+    }
+}
@@ -0,0 +1,272 @@
+use num::rational::Ratio;
+use num::{Integer, Num, Signed};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LinearSolution<T> {
+    Unique(Vec<T>),
+    /// There are infinitely many solutions. `particular` is the one where every free
+    /// variable is zero.
+    Underdetermined {
+        particular: Vec<T>,
+        free: Vec<usize>,
+    },
+    Inconsistent,
+}
+
+impl<T> LinearSolution<T> {
+    pub fn unique(self) -> Option<Vec<T>> {
+        match self {
+            LinearSolution::Unique(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// Solve `a * x = b` with Gauss-Jordan elimination. It needs exact division, so use it with
+/// `Rational64`, `BigRational` and the like.
+pub fn solve_rational<T>(a: &[Vec<T>], b: &[T]) -> LinearSolution<T>
+where
+    T: Num + Clone,
+{
+    let vars = a.first().map(|row| row.len()).unwrap_or(0);
+    let mut rows = augmented(a, b);
+    let pivots = reduce_rows(&mut rows, vars);
+
+    read_solution(&rows, &pivots, vars)
+}
+
+/// Solve `a * x = b` over the integers with Bareiss' fraction-free elimination, so the
+/// only fractions are in the final back-substitution. Pick a `T` that fits the
+/// determinants of the system, since the intermediate values grow that large.
+pub fn solve_integer<T>(a: &[Vec<T>], b: &[T]) -> LinearSolution<Ratio<T>>
+where
+    T: Integer + Signed + Clone,
+{
+    let vars = a.first().map(|row| row.len()).unwrap_or(0);
+    let mut rows = augmented(a, b);
+    bareiss(&mut rows, vars);
+
+    let mut rows: Vec<Vec<Ratio<T>>> = rows
+        .into_iter()
+        .map(|row| row.into_iter().map(Ratio::from_integer).collect())
+        .collect();
+    let pivots = reduce_rows(&mut rows, vars);
+
+    read_solution(&rows, &pivots, vars)
+}
+
+/// The solution of `a * x = b` if there is exactly one and it's all non-negative
+/// integers, like the button presses on day 13.
+pub fn solve_non_negative_integer<T>(a: &[Vec<T>], b: &[T]) -> Option<Vec<T>>
+where
+    T: Integer + Signed + Clone,
+{
+    solve_integer(a, b)
+        .unique()?
+        .into_iter()
+        .map(|v| (v.is_integer() && !v.is_negative()).then(|| v.to_integer()))
+        .collect()
+}
+
+/// The determinant of a square matrix, with Bareiss' algorithm.
+pub fn determinant<T>(matrix: &[Vec<T>]) -> T
+where
+    T: Integer + Signed + Clone,
+{
+    let n = matrix.len();
+    if n == 0 {
+        return T::one();
+    }
+
+    let mut rows = matrix.to_vec();
+    let (pivots, swaps) = bareiss(&mut rows, n);
+    if pivots.len() < n {
+        return T::zero();
+    }
+
+    let det = rows[n - 1][n - 1].clone();
+    if swaps % 2 == 1 {
+        -det
+    } else {
+        det
+    }
+}
+
+fn augmented<T: Clone>(a: &[Vec<T>], b: &[T]) -> Vec<Vec<T>> {
+    a.iter()
+        .zip(b.iter())
+        .map(|(row, b)| {
+            let mut row = row.clone();
+            row.push(b.clone());
+            row
+        })
+        .collect()
+}
+
+/// Fraction-free elimination into row echelon form over the first `cols` columns. Every
+/// entry stays a minor of the original matrix, so the divisions are exact. Returns the
+/// pivot columns and the number of row swaps.
+fn bareiss<T>(rows: &mut [Vec<T>], cols: usize) -> (Vec<usize>, usize)
+where
+    T: Integer + Clone,
+{
+    let mut pivots = Vec::new();
+    let mut swaps = 0;
+    let mut prev = T::one();
+    for c in 0..cols {
+        let r = pivots.len();
+        let Some(p) = (r..rows.len()).find(|i| !rows[*i][c].is_zero()) else {
+            continue;
+        };
+        if p != r {
+            rows.swap(r, p);
+            swaps += 1;
+        }
+
+        let pivot_row = rows[r].clone();
+        for row in rows[r + 1..].iter_mut() {
+            let factor = row[c].clone();
+            for j in c + 1..row.len() {
+                row[j] = (row[j].clone() * pivot_row[c].clone()
+                    - factor.clone() * pivot_row[j].clone())
+                    / prev.clone();
+            }
+            row[c] = T::zero();
+        }
+
+        prev = pivot_row[c].clone();
+        pivots.push(c);
+    }
+
+    (pivots, swaps)
+}
+
+/// Gauss-Jordan into reduced row echelon form over the first `cols` columns, returning
+/// the pivot columns.
+fn reduce_rows<T>(rows: &mut [Vec<T>], cols: usize) -> Vec<usize>
+where
+    T: Num + Clone,
+{
+    let mut pivots = Vec::new();
+    for c in 0..cols {
+        let r = pivots.len();
+        let Some(p) = (r..rows.len()).find(|i| !rows[*i][c].is_zero()) else {
+            continue;
+        };
+        rows.swap(r, p);
+
+        let pivot = rows[r][c].clone();
+        for v in rows[r].iter_mut() {
+            *v = v.clone() / pivot.clone();
+        }
+
+        let pivot_row = rows[r].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i == r || row[c].is_zero() {
+                continue;
+            }
+
+            let factor = row[c].clone();
+            for (v, p) in row.iter_mut().zip(pivot_row.iter()) {
+                *v = v.clone() - factor.clone() * p.clone();
+            }
+        }
+
+        pivots.push(c);
+    }
+
+    pivots
+}
+
+fn read_solution<T>(rows: &[Vec<T>], pivots: &[usize], vars: usize) -> LinearSolution<T>
+where
+    T: Num + Clone,
+{
+    if rows[pivots.len()..].iter().any(|row| !row[vars].is_zero()) {
+        return LinearSolution::Inconsistent;
+    }
+
+    let mut values = vec![T::zero(); vars];
+    for (row, c) in rows.iter().zip(pivots.iter()) {
+        values[*c] = row[vars].clone();
+    }
+
+    if pivots.len() == vars {
+        LinearSolution::Unique(values)
+    } else {
+        LinearSolution::Underdetermined {
+            particular: values,
+            free: (0..vars).filter(|c| !pivots.contains(c)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::{BigInt, BigRational, Rational64};
+
+    fn r(n: i64, d: i64) -> Rational64 {
+        Rational64::new(n, d)
+    }
+
+    #[test]
+    fn rational_systems() {
+        let a = vec![
+            vec![r(2, 1), r(1, 1), r(-1, 1)],
+            vec![r(-3, 1), r(-1, 1), r(2, 1)],
+            vec![r(-2, 1), r(1, 1), r(2, 1)],
+        ];
+        let b = [r(8, 1), r(-11, 1), r(-3, 1)];
+        assert_eq!(
+            solve_rational(&a, &b),
+            LinearSolution::Unique(vec![r(2, 1), r(3, 1), r(-1, 1)])
+        );
+
+        let a = vec![vec![r(1, 2), r(1, 1)], vec![r(1, 1), r(2, 1)]];
+        assert_eq!(
+            solve_rational(&a, &[r(1, 1), r(3, 1)]),
+            LinearSolution::Inconsistent
+        );
+        assert_eq!(
+            solve_rational(&a, &[r(1, 1), r(2, 1)]),
+            LinearSolution::Underdetermined {
+                particular: vec![r(2, 1), r(0, 1)],
+                free: vec![1],
+            }
+        );
+
+        let big = |n: i64| BigRational::from_integer(BigInt::from(n));
+        let a = vec![vec![big(1), big(1)], vec![big(1), big(-1)]];
+        let x = solve_rational(&a, &[big(10), big(4)]).unique().unwrap();
+        assert_eq!(x, vec![big(7), big(3)]);
+    }
+
+    #[test]
+    fn integer_systems() {
+        let a = vec![vec![2i64, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        assert_eq!(determinant(&a), -1);
+        assert_eq!(determinant(&[vec![0i64, 1], vec![1, 0]]), -1);
+        assert_eq!(determinant(&[vec![1i64, 2], vec![2, 4]]), 0);
+        assert_eq!(
+            solve_non_negative_integer(&a, &[8, -11, -3]),
+            None,
+            "-1 isn't non-negative"
+        );
+
+        let a = vec![vec![94i64, 22], vec![34, 67]];
+        assert_eq!(
+            solve_non_negative_integer(&a, &[8400, 5400]),
+            Some(vec![80, 40])
+        );
+        let a = vec![vec![26i64, 67], vec![66, 21]];
+        assert_eq!(solve_non_negative_integer(&a, &[12748, 12176]), None);
+        assert_eq!(
+            solve_integer(&[vec![2i64, 4], vec![1, 2]], &[6, 3]),
+            LinearSolution::Underdetermined {
+                particular: vec![Ratio::new(3, 1), Ratio::new(0, 1)],
+                free: vec![1],
+            }
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use crate::search::Key;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// Storage for `Memo`. The getter takes `&mut self` so the LRU can bump entries.
+pub trait MemoCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<V>;
+    fn insert(&mut self, key: K, value: V);
+    fn clear(&mut self);
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K, V> MemoCache<K, V> for FxHashMap<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    #[inline]
+    fn get(&mut self, key: &K) -> Option<V> {
+        FxHashMap::get(self, key).cloned()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
+        FxHashMap::insert(self, key, value);
+    }
+
+    fn clear(&mut self) {
+        FxHashMap::clear(self);
+    }
+
+    fn len(&self) -> usize {
+        FxHashMap::len(self)
+    }
+}
+
+/// A dense cache for keys that map to small indices. It grows to fit the largest key.
+impl<K, V> MemoCache<K, V> for Vec<Option<V>>
+where
+    K: Key<usize>,
+    V: Clone,
+{
+    #[inline]
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.as_slice().get(key.key()).cloned().flatten()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
+        let index = key.key();
+        if index >= self.as_slice().len() {
+            self.resize(index + 1, None);
+        }
+
+        self[index] = Some(value);
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+
+    fn len(&self) -> usize {
+        self.iter().filter(|v| v.is_some()).count()
+    }
+}
+
+/// A cache that only keeps the `capacity` most recently used entries.
+pub struct LruCache<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: FxHashMap<K, (V, u64)>,
+    order: BTreeMap<u64, K>,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            tick: 0,
+            entries: FxHashMap::default(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<K, V> MemoCache<K, V> for LruCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    fn get(&mut self, key: &K) -> Option<V> {
+        let (value, used) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        self.tick += 1;
+        *used = self.tick;
+        self.order.insert(self.tick, key.clone());
+
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.insert(key.clone(), (value, self.tick)) {
+            self.order.remove(&used);
+        } else if self.entries.len() > self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.order.insert(self.tick, key);
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.hits + self.misses;
+        let rate = if total > 0 {
+            self.hits as f64 * 100.0 / total as f64
+        } else {
+            0.0
+        };
+
+        write!(f, "{} hits, {} misses ({rate:.1}%)", self.hits, self.misses)
+    }
+}
+
+pub type MemoFn<'f, K, V, C> = &'f dyn Fn(&mut Memo<'f, K, V, C>, K) -> V;
+
+/// A memoized recursive function. The function gets the `Memo` back so it can recurse
+/// through `get`, which makes it possible to memoize closures that capture the input.
+pub struct Memo<'f, K, V, C = FxHashMap<K, V>> {
+    cache: C,
+    f: MemoFn<'f, K, V, C>,
+    stats: MemoStats,
+}
+
+impl<'f, K, V, C> Memo<'f, K, V, C>
+where
+    K: Clone,
+    V: Clone,
+    C: MemoCache<K, V>,
+{
+    pub fn new(f: MemoFn<'f, K, V, C>) -> Self
+    where
+        C: Default,
+    {
+        Self::with_cache(C::default(), f)
+    }
+
+    pub fn with_cache(cache: C, f: MemoFn<'f, K, V, C>) -> Self {
+        Self {
+            cache,
+            f,
+            stats: MemoStats::default(),
+        }
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value;
+        }
+
+        self.stats.misses += 1;
+        let f = self.f;
+        let value = f(self, key.clone());
+        self.cache.insert(key, value.clone());
+
+        value
+    }
+
+    #[inline]
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    #[inline]
+    pub fn cache(&self) -> &C {
+        &self.cache
+    }
+
+    /// Clear the cache and the stats.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = MemoStats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fibonacci_backends() {
+        let fib = |memo: &mut Memo<u64, u64>, n: u64| {
+            if n < 2 {
+                n
+            } else {
+                memo.get(n - 1) + memo.get(n - 2)
+            }
+        };
+        let mut memo = Memo::new(&fib);
+        assert_eq!(memo.get(90), 2880067194370816120);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 88,
+                misses: 91
+            }
+        );
+        assert_eq!(memo.cache().len(), 91);
+
+        let fib = |memo: &mut Memo<usize, u64, Vec<Option<u64>>>, n: usize| {
+            if n < 2 {
+                n as u64
+            } else {
+                memo.get(n - 1) + memo.get(n - 2)
+            }
+        };
+        let mut memo = Memo::new(&fib);
+        assert_eq!(memo.get(90), 2880067194370816120);
+        assert_eq!(memo.stats().misses, 91);
+        memo.clear();
+        assert_eq!(memo.stats(), MemoStats::default());
+        assert!(memo.cache().is_empty());
+    }
+
+    fn towel_ways<'a>(towels: &[&[u8]], design: &'a [u8]) -> (u64, usize) {
+        type TowelMemo<'f, 'a> = Memo<'f, &'a [u8], u64, LruCache<&'a [u8], u64>>;
+
+        let ways = |memo: &mut TowelMemo<'_, 'a>, s: &'a [u8]| {
+            if s.is_empty() {
+                return 1;
+            }
+
+            towels
+                .iter()
+                .filter(|t| s.starts_with(t))
+                .map(|t| memo.get(&s[t.len()..]))
+                .sum()
+        };
+        let mut memo = Memo::with_cache(LruCache::new(4), &ways);
+        (memo.get(design), memo.cache().len())
+    }
+
+    #[test]
+    fn captures_and_lru() {
+        let (ways, cached) = towel_ways(&[b"r", b"wr", b"rw"], b"rwrwr");
+        assert_eq!(ways, 3);
+        assert!(cached <= 4);
+
+        let mut lru = LruCache::new(2);
+        lru.insert(1, 'a');
+        lru.insert(2, 'b');
+        assert_eq!(lru.get(&1), Some('a'));
+        lru.insert(3, 'c');
+        assert_eq!(lru.get(&2), None);
+        assert_eq!(lru.get(&1), Some('a'));
+        assert_eq!(lru.len(), 2);
+    }
+}
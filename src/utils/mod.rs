@@ -1,9 +1,21 @@
-pub use crt::crt;
-pub use direction::CardinalDirection;
+pub use crt::{crt, crt_big, crt_generalized};
+pub use cycle::{brent, fast_forward, find_cycle, floyd};
+pub use direction::{CardinalDirection, DirectionSet, OrdinalDirection};
 pub use gather::GatherTarget;
-pub use union_find::UnionFind;
+pub use keypad::{Keypad, RobotChain};
+pub use linear::{
+    determinant, solve_integer, solve_non_negative_integer, solve_rational, LinearSolution,
+};
+pub use memo::{LruCache, Memo, MemoCache, MemoFn, MemoStats};
+pub use number::{egcd, gcd, lcm, mod_inv, mod_pow, prime_factors, sieve, ModInt};
+pub use union_find::{KeyedUnionFind, UnionFind};
 
 mod crt;
+mod cycle;
 mod direction;
 mod gather;
+mod keypad;
+mod linear;
+mod memo;
+mod number;
 mod union_find;
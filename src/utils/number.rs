@@ -0,0 +1,279 @@
+use num::{Integer, Signed};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Extended Euclid, giving `(g, x, y)` where `a * x + b * y = g = gcd(a, b)`.
+pub fn egcd<T>(a: T, b: T) -> (T, T, T)
+where
+    T: Integer + Clone,
+{
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while !r.is_zero() {
+        let q = old_r.div_floor(&r);
+        let next_r = old_r - q.clone() * r.clone();
+        let next_x = old_x - q.clone() * x.clone();
+        let next_y = old_y - q * y.clone();
+        (old_r, r) = (r, next_r);
+        (old_x, x) = (x, next_x);
+        (old_y, y) = (y, next_y);
+    }
+
+    (old_r, old_x, old_y)
+}
+
+/// The inverse of `x` modulo `m`, if they're coprime.
+pub fn mod_inv<T>(x: T, m: T) -> Option<T>
+where
+    T: Integer + Signed + Clone,
+{
+    let (g, x, _) = egcd(x.mod_floor(&m), m.clone());
+    if g.is_one() {
+        Some(x.mod_floor(&m))
+    } else {
+        None
+    }
+}
+
+/// `base^exp mod m`, with the products done in `u128` so it can't overflow.
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    if m == 1 {
+        return 0;
+    }
+
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut res = 1u128;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = res * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    res as u64
+}
+
+#[inline]
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    a.gcd(&b)
+}
+
+#[inline]
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    a.lcm(&b)
+}
+
+/// All primes up to and including `n`.
+pub fn sieve(n: usize) -> Vec<usize> {
+    if n < 2 {
+        return Vec::new();
+    }
+
+    let mut composite = vec![false; n + 1];
+    let mut primes = Vec::new();
+    for i in 2..=n {
+        if composite[i] {
+            continue;
+        }
+
+        primes.push(i);
+        for j in (i * i..=n).step_by(i) {
+            composite[j] = true;
+        }
+    }
+
+    primes
+}
+
+/// The prime factors of `n` with their exponents, smallest first.
+pub fn prime_factors(mut n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+    while p <= n / p {
+        if n.is_multiple_of(p) {
+            let mut exp = 0;
+            while n.is_multiple_of(p) {
+                n /= p;
+                exp += 1;
+            }
+            factors.push((p, exp));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+
+    factors
+}
+
+/// An integer modulo `M`. The products go through `u128`, so any `M` up to `u64::MAX`
+/// works.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    #[inline]
+    pub fn new(value: u64) -> Self {
+        Self(value % M)
+    }
+
+    #[inline]
+    pub fn from_signed(value: i64) -> Self {
+        Self((value as i128).rem_euclid(M as i128) as u64)
+    }
+
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    pub fn pow(&self, exp: u64) -> Self {
+        Self(mod_pow(self.0, exp, M))
+    }
+
+    /// The multiplicative inverse, which only exists if the value is coprime with `M`.
+    #[inline]
+    pub fn inv(&self) -> Option<Self> {
+        mod_inv(self.0 as i128, M as i128).map(|v| Self(v as u64))
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const M: u64> fmt::Debug for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.0, M)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(((self.0 as u128 + rhs.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.0 == 0 {
+            self
+        } else {
+            Self(M - self.0)
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self((self.0 as u128 * rhs.0 as u128 % M as u128) as u64)
+    }
+}
+
+/// Panics if `rhs` has no inverse.
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Mul::mul(self, rhs.inv().expect("divisor is not invertible"))
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn euclid_and_inverses() {
+        let (g, x, y) = egcd(240i64, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inv(3i64, 11), Some(4));
+        assert_eq!(mod_inv(-3i64, 11), Some(7));
+        assert_eq!(mod_inv(6i64, 9), None);
+        assert_eq!(mod_pow(2, 62, u64::MAX), 1 << 62);
+        assert_eq!(mod_pow(3, 1_000_000_006, 1_000_000_007), 1);
+        assert_eq!(lcm(gcd(12u32, 18), 4), 12);
+    }
+
+    #[test]
+    fn primes() {
+        assert_eq!(sieve(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(prime_factors(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(prime_factors(1_000_000_007), vec![(1_000_000_007, 1)]);
+        assert!(prime_factors(1).is_empty());
+        assert_eq!(
+            prime_factors(u64::MAX),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6700417, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn mod_int() {
+        type M7 = ModInt<7>;
+        let a = M7::new(5);
+        assert_eq!((a + M7::new(4)).value(), 2);
+        assert_eq!((a - M7::new(6)).value(), 6);
+        assert_eq!((a * a).value(), 4);
+        assert_eq!(a / a, M7::new(1));
+        assert_eq!(a.inv(), Some(M7::new(3)));
+        assert_eq!(a.pow(6), M7::new(1));
+        assert_eq!(M7::from_signed(-1), M7::new(6));
+
+        let big = ModInt::<{ u64::MAX - 58 }>::new(u64::MAX - 60);
+        assert_eq!((big * big).value(), 4);
+    }
+}
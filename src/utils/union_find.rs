@@ -1,18 +1,65 @@
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
 pub struct UnionFind {
     parents: Vec<usize>,
-    ranks: Vec<u32>,
+    ranks: Vec<u8>,
+    sizes: Vec<u32>,
+    groups: usize,
 }
 
 impl UnionFind {
     pub fn new(size: usize) -> Self {
         let parents = (0..size).collect();
-        let ranks = vec![1; size];
+        let ranks = vec![0; size];
+        let sizes = vec![1; size];
+
+        Self {
+            parents,
+            ranks,
+            sizes,
+            groups: size,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
 
-        Self { parents, ranks }
+    /// Add a new element in a group of its own, returning its index.
+    pub fn push(&mut self) -> usize {
+        let index = self.parents.len();
+        self.parents.push(index);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.groups += 1;
+
+        index
+    }
+
+    /// Find the root of the group, halving the path on the way up.
+    #[inline]
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut current = x;
+        while current != self.parents[current] {
+            let grandparent = self.parents[self.parents[current]];
+            self.parents[current] = grandparent;
+            current = grandparent;
+        }
+
+        current
     }
 
+    /// Find the root of the group without touching the paths, for when only a shared
+    /// reference is available.
     #[inline]
-    pub fn find(&self, x: usize) -> usize {
+    pub fn root(&self, x: usize) -> usize {
         let mut current = self.parents[x];
         while current != self.parents[current] {
             current = self.parents[current];
@@ -22,12 +69,20 @@ impl UnionFind {
     }
 
     #[inline]
-    pub fn group_count(&mut self) -> usize {
-        self.parents
-            .iter()
-            .enumerate()
-            .filter(|(i, p)| **p == *i)
-            .count()
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    #[inline]
+    pub fn group_count(&self) -> usize {
+        self.groups
+    }
+
+    /// The number of elements in the same group as `x`.
+    #[inline]
+    pub fn group_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root] as usize
     }
 
     pub fn union(&mut self, x: usize, y: usize) -> bool {
@@ -35,19 +90,144 @@ impl UnionFind {
         let py = self.find(y);
 
         if px != py {
-            if self.ranks[py] <= self.ranks[px] {
-                self.parents[px] = py;
-                self.ranks[py] += 1;
+            let (child, root) = if self.ranks[px] < self.ranks[py] {
+                (px, py)
             } else {
-                self.parents[py] = px;
-                self.ranks[px] += 1;
+                (py, px)
+            };
+            if self.ranks[child] == self.ranks[root] {
+                self.ranks[root] += 1;
             }
 
+            self.parents[child] = root;
+            self.sizes[root] += self.sizes[child];
+            self.groups -= 1;
+
             true
         } else {
             false
         }
     }
+
+    /// The members of every group, in the order their first member shows up.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut group_indices = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.groups);
+        for i in 0..self.len() {
+            let root = self.find(i);
+            if group_indices[root] == usize::MAX {
+                group_indices[root] = groups.len();
+                groups.push(Vec::with_capacity(self.sizes[root] as usize));
+            }
+
+            groups[group_indices[root]].push(i);
+        }
+
+        groups
+    }
+}
+
+/// A `UnionFind` over any hashable key, interning them into indices as they show up.
+pub struct KeyedUnionFind<K> {
+    inner: UnionFind,
+    indices: FxHashMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K> KeyedUnionFind<K>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            inner: UnionFind::new(0),
+            indices: FxHashMap::default(),
+            keys: Vec::new(),
+        }
+    }
+
+    /// Get the index of the key, adding it in a group of its own if it's new.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(index) = self.indices.get(&key) {
+            return *index;
+        }
+
+        let index = self.inner.push();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key);
+        index
+    }
+
+    #[inline]
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        self.indices.get(key).copied()
+    }
+
+    #[inline]
+    pub fn key_of(&self, index: usize) -> &K {
+        &self.keys[index]
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The root key of the group, if the key has been seen.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let index = self.index_of(key)?;
+        Some(&self.keys[self.inner.find(index)])
+    }
+
+    /// Join the groups of the keys, adding them if they're new.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.insert(a);
+        let b = self.insert(b);
+        self.inner.union(a, b)
+    }
+
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.index_of(a), self.index_of(b)) {
+            (Some(a), Some(b)) => self.inner.same(a, b),
+            _ => a == b,
+        }
+    }
+
+    #[inline]
+    pub fn group_count(&self) -> usize {
+        self.inner.group_count()
+    }
+
+    /// The size of the key's group, which is 0 if it hasn't been seen.
+    pub fn group_size(&mut self, key: &K) -> usize {
+        match self.index_of(key) {
+            Some(index) => self.inner.group_size(index),
+            None => 0,
+        }
+    }
+
+    /// The keys of every group, in insertion order.
+    pub fn groups(&mut self) -> Vec<Vec<K>> {
+        self.inner
+            .groups()
+            .into_iter()
+            .map(|group| group.into_iter().map(|i| self.keys[i].clone()).collect())
+            .collect()
+    }
+}
+
+impl<K> Default for KeyedUnionFind<K>
+where
+    K: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -70,6 +250,49 @@ mod tests {
         assert_ne!(uf.find(2), uf.find(3));
     }
 
+    #[test]
+    fn sizes_and_groups() {
+        let mut uf = UnionFind::new(7);
+        uf.union(0, 3);
+        uf.union(3, 6);
+        uf.union(2, 5);
+        assert!(!uf.union(6, 0));
+
+        assert_eq!(uf.group_count(), 4);
+        assert_eq!(uf.group_size(6), 3);
+        assert_eq!(uf.group_size(1), 1);
+        assert_eq!(uf.root(0), uf.find(6));
+        assert_eq!(
+            uf.groups(),
+            vec![vec![0, 3, 6], vec![1], vec![2, 5], vec![4]]
+        );
+
+        let index = uf.push();
+        uf.union(index, 4);
+        assert_eq!(uf.group_size(4), 2);
+        assert_eq!(uf.group_count(), 4);
+    }
+
+    #[test]
+    fn keyed() {
+        let mut uf = KeyedUnionFind::new();
+        uf.union("kh", "tc");
+        uf.union("qp", "kh");
+        uf.union("de", "cg");
+        uf.insert("yn");
+
+        assert!(uf.same(&"tc", &"qp"));
+        assert!(!uf.same(&"tc", &"de"));
+        assert!(!uf.same(&"tc", &"nope"));
+        assert_eq!(uf.group_size(&"qp"), 3);
+        assert_eq!(uf.group_size(&"nope"), 0);
+        assert_eq!(uf.group_count(), 3);
+        assert_eq!(
+            uf.groups(),
+            vec![vec!["kh", "tc", "qp"], vec!["de", "cg"], vec!["yn"]]
+        );
+    }
+
     #[test]
     fn ram_run_example() {}
 }
@@ -0,0 +1,107 @@
+use super::InstructionSet;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AsmError {
+    UnknownMnemonic(usize, String),
+    BadOperand(usize, String),
+    WrongOperandCount(usize, usize),
+}
+
+/// One instruction per line, prefixed with its address, the same way day 17 printed it.
+/// Words that don't decode are printed as `??`.
+pub fn disassemble<I: InstructionSet>(program: &[I::Word]) -> String {
+    let mut res = String::with_capacity(program.len() * 8);
+    let mut pc = 0;
+    while pc < program.len() {
+        write!(res, "{pc}").unwrap();
+        match I::op_by_opcode(program[pc]) {
+            Some(op) if pc + op.operands.len() < program.len() => {
+                write!(res, " {}", op.mnemonic).unwrap();
+                for (kind, value) in op.operands.iter().zip(&program[pc + 1..]) {
+                    write!(res, " {}", I::operand_name(*kind, *value)).unwrap();
+                }
+                pc += 1 + op.operands.len();
+            }
+            _ => {
+                write!(res, " ?? {}", program[pc]).unwrap();
+                pc += 1;
+            }
+        }
+        res.push('\n');
+    }
+
+    res
+}
+
+/// Turn mnemonics back into a program. Blank lines and anything after a `;` are skipped,
+/// and so is a leading address, so the disassembly goes back in as-is. Errors have the
+/// line number, counting from 1.
+pub fn assemble<I: InstructionSet>(source: &str) -> Result<Vec<I::Word>, AsmError> {
+    let mut program = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.split(';').next().unwrap_or("");
+        let mut tokens = line.split_whitespace().peekable();
+        if tokens
+            .peek()
+            .is_some_and(|t| t.bytes().all(|b| b.is_ascii_digit()))
+        {
+            tokens.next();
+        }
+
+        let Some(mnemonic) = tokens.next() else {
+            continue;
+        };
+        let op = I::op_by_mnemonic(mnemonic)
+            .ok_or_else(|| AsmError::UnknownMnemonic(line_no, mnemonic.to_owned()))?;
+
+        let operands: Vec<&str> = tokens.collect();
+        if operands.len() != op.operands.len() {
+            return Err(AsmError::WrongOperandCount(line_no, operands.len()));
+        }
+
+        program.push(op.opcode);
+        for (kind, s) in op.operands.iter().zip(operands) {
+            let value = I::parse_operand(*kind, s)
+                .ok_or_else(|| AsmError::BadOperand(line_no, s.to_owned()))?;
+            program.push(value);
+        }
+    }
+
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::ThreeBit;
+
+    #[test]
+    fn round_trip() {
+        let program = [2, 4, 1, 1, 7, 5, 4, 0, 0, 3, 1, 6, 5, 5, 3, 0];
+        let text = disassemble::<ThreeBit>(&program);
+        assert!(text.starts_with("0 BST a\n2 BXL 1\n4 CDV b\n6 BXC 0\n"));
+        assert_eq!(assemble::<ThreeBit>(&text), Ok(program.to_vec()));
+
+        let source = "adv 3 ; a /= 8\n\nout a\njnz 0\n";
+        assert_eq!(assemble::<ThreeBit>(source), Ok(vec![0, 3, 5, 4, 3, 0]));
+        assert_eq!(disassemble::<ThreeBit>(&[5]), "0 ?? 5\n");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            assemble::<ThreeBit>("adv 1\nmul 2"),
+            Err(AsmError::UnknownMnemonic(2, "mul".to_owned()))
+        );
+        assert_eq!(
+            assemble::<ThreeBit>("bxl 9"),
+            Err(AsmError::BadOperand(1, "9".to_owned()))
+        );
+        assert_eq!(
+            assemble::<ThreeBit>("out"),
+            Err(AsmError::WrongOperandCount(1, 0))
+        );
+    }
+}
@@ -0,0 +1,234 @@
+mod asm;
+mod three_bit;
+
+use std::fmt;
+use std::str::FromStr;
+
+pub use asm::{assemble, disassemble, AsmError};
+pub use three_bit::ThreeBit;
+
+/// What an operand means, so the disassembler can name it and the ISA knows how to read
+/// it. `Combo` is whatever the ISA decides, like day 17's literal-or-register operands.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OperandKind {
+    Literal,
+    Register,
+    Combo,
+    Address,
+}
+
+/// One entry in an instruction set's table.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct OpDef<W: 'static> {
+    pub opcode: W,
+    pub mnemonic: &'static str,
+    pub operands: &'static [OperandKind],
+}
+
+/// An instruction and the operands that follow it in the program.
+pub type Decoded<'p, W> = (&'static OpDef<W>, &'p [W]);
+
+/// Where the program counter goes after an instruction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Flow {
+    Next,
+    Jump(usize),
+    Halt,
+}
+
+/// Why `Machine::run` stopped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Stop {
+    /// The program counter left the program, or an instruction halted it.
+    Halted,
+    /// About to run the instruction at the breakpoint.
+    Breakpoint(usize),
+    /// The trace hook asked to stop before the instruction at this address.
+    Hook(usize),
+    /// Hit an opcode that isn't in the table.
+    BadOpcode(usize),
+}
+
+/// A register machine's instruction set. The table drives decoding, the assembler and the
+/// disassembler, so `execute` only needs to do the actual work.
+pub trait InstructionSet: Sized + 'static {
+    type Word: Copy + PartialEq + fmt::Display + FromStr + 'static;
+    type Registers: Clone + fmt::Debug;
+    type Output: Clone + PartialEq;
+
+    const OPS: &'static [OpDef<Self::Word>];
+
+    fn execute(m: &mut Machine<'_, Self>, op: &OpDef<Self::Word>, args: &[Self::Word]) -> Flow;
+
+    /// How an operand shows up in the disassembly.
+    fn operand_name(_kind: OperandKind, value: Self::Word) -> String {
+        value.to_string()
+    }
+
+    /// The reverse of `operand_name`.
+    fn parse_operand(_kind: OperandKind, s: &str) -> Option<Self::Word> {
+        s.parse().ok()
+    }
+
+    fn op_by_opcode(opcode: Self::Word) -> Option<&'static OpDef<Self::Word>> {
+        Self::OPS.iter().find(|op| op.opcode == opcode)
+    }
+
+    fn op_by_mnemonic(mnemonic: &str) -> Option<&'static OpDef<Self::Word>> {
+        Self::OPS
+            .iter()
+            .find(|op| op.mnemonic.eq_ignore_ascii_case(mnemonic))
+    }
+}
+
+pub struct Machine<'p, I: InstructionSet> {
+    pub program: &'p [I::Word],
+    pub pc: usize,
+    pub registers: I::Registers,
+    pub output: Vec<I::Output>,
+    pub steps: usize,
+    breakpoints: Vec<usize>,
+}
+
+impl<'p, I: InstructionSet> Machine<'p, I> {
+    pub fn new(program: &'p [I::Word], registers: I::Registers) -> Self {
+        Self {
+            program,
+            pc: 0,
+            registers,
+            output: Vec::new(),
+            steps: 0,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    /// Start over with new registers, keeping the output buffer's allocation.
+    pub fn reset(&mut self, registers: I::Registers) {
+        self.pc = 0;
+        self.registers = registers;
+        self.output.clear();
+        self.steps = 0;
+    }
+
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        if !self.breakpoints.contains(&pc) {
+            self.breakpoints.push(pc);
+        }
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// The instruction at `pc` and its operands, if it's complete and in the table.
+    pub fn decode(&self, pc: usize) -> Option<Decoded<'p, I::Word>> {
+        let op = I::op_by_opcode(*self.program.get(pc)?)?;
+        let args = self.program.get(pc + 1..pc + 1 + op.operands.len())?;
+        Some((op, args))
+    }
+
+    #[inline]
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Run one instruction. Returns `Some` if the machine stopped.
+    pub fn step(&mut self) -> Option<Stop> {
+        if self.is_halted() {
+            return Some(Stop::Halted);
+        }
+
+        let Some((op, args)) = self.decode(self.pc) else {
+            return Some(Stop::BadOpcode(self.pc));
+        };
+
+        self.steps += 1;
+        match I::execute(self, op, args) {
+            Flow::Next => self.pc += 1 + args.len(),
+            Flow::Jump(target) => self.pc = target,
+            Flow::Halt => self.pc = self.program.len(),
+        }
+
+        self.is_halted().then_some(Stop::Halted)
+    }
+
+    /// Run until it halts or hits a breakpoint. A breakpoint at the current address is
+    /// skipped, so calling it again continues past it.
+    pub fn run(&mut self) -> Stop {
+        self.run_traced(|_, _, _| true)
+    }
+
+    /// Like `run`, but calls the hook before every instruction with the machine, the
+    /// instruction and its operands. Returning false stops it.
+    pub fn run_traced<F>(&mut self, mut hook: F) -> Stop
+    where
+        F: FnMut(&Self, &OpDef<I::Word>, &[I::Word]) -> bool,
+    {
+        let mut first = true;
+        loop {
+            if !first && self.breakpoints.contains(&self.pc) {
+                return Stop::Breakpoint(self.pc);
+            }
+            first = false;
+
+            if let Some((op, args)) = self.decode(self.pc) {
+                if !hook(self, op, args) {
+                    return Stop::Hook(self.pc);
+                }
+            }
+
+            if let Some(stop) = self.step() {
+                return stop;
+            }
+        }
+    }
+}
+
+/// Find the smallest input that makes the program print `target`, for programs that chew
+/// through their input `radix` digits at a time and print one output per digit, like day
+/// 17. It picks the digits from the most significant one, checking that the output ends
+/// with the matching part of the target before going deeper.
+pub fn reverse_search<I, F>(
+    machine: &mut Machine<'_, I>,
+    target: &[I::Output],
+    radix: u64,
+    registers: F,
+) -> Option<u64>
+where
+    I: InstructionSet,
+    F: Fn(u64) -> I::Registers,
+{
+    fn search<I, F>(
+        machine: &mut Machine<'_, I>,
+        target: &[I::Output],
+        radix: u64,
+        registers: &F,
+        prefix: u64,
+        matched: usize,
+    ) -> Option<u64>
+    where
+        I: InstructionSet,
+        F: Fn(u64) -> I::Registers,
+    {
+        if matched == target.len() {
+            return Some(prefix);
+        }
+
+        (0..radix).find_map(|digit| {
+            let input = prefix * radix + digit;
+            if input == 0 {
+                return None;
+            }
+
+            machine.reset(registers(input));
+            machine.run();
+            if machine.output == target[target.len() - matched - 1..] {
+                search(machine, target, radix, registers, input, matched + 1)
+            } else {
+                None
+            }
+        })
+    }
+
+    search(machine, target, radix, &registers, 0, 0)
+}
@@ -0,0 +1,167 @@
+use super::{Flow, InstructionSet, Machine, OpDef, OperandKind};
+
+const LITERAL: &[OperandKind] = &[OperandKind::Literal];
+const COMBO: &[OperandKind] = &[OperandKind::Combo];
+const ADDRESS: &[OperandKind] = &[OperandKind::Address];
+
+/// Day 17's 3-bit computer, with registers `a`, `b` and `c`. Combo operands 0-3 are
+/// literals, and 4-6 read the registers.
+pub struct ThreeBit;
+
+impl ThreeBit {
+    #[inline]
+    fn combo(code: u8, registers: &[u64; 3]) -> u64 {
+        if code < 4 {
+            code as u64
+        } else {
+            registers[(code - 4) as usize]
+        }
+    }
+}
+
+impl InstructionSet for ThreeBit {
+    type Word = u8;
+    type Registers = [u64; 3];
+    type Output = u8;
+
+    const OPS: &'static [OpDef<u8>] = &[
+        OpDef {
+            opcode: 0,
+            mnemonic: "ADV",
+            operands: COMBO,
+        },
+        OpDef {
+            opcode: 1,
+            mnemonic: "BXL",
+            operands: LITERAL,
+        },
+        OpDef {
+            opcode: 2,
+            mnemonic: "BST",
+            operands: COMBO,
+        },
+        OpDef {
+            opcode: 3,
+            mnemonic: "JNZ",
+            operands: ADDRESS,
+        },
+        OpDef {
+            opcode: 4,
+            mnemonic: "BXC",
+            // It has an operand, but ignores it.
+            operands: LITERAL,
+        },
+        OpDef {
+            opcode: 5,
+            mnemonic: "OUT",
+            operands: COMBO,
+        },
+        OpDef {
+            opcode: 6,
+            mnemonic: "BDV",
+            operands: COMBO,
+        },
+        OpDef {
+            opcode: 7,
+            mnemonic: "CDV",
+            operands: COMBO,
+        },
+    ];
+
+    fn execute(m: &mut Machine<'_, Self>, op: &OpDef<u8>, args: &[u8]) -> Flow {
+        let r = &mut m.registers;
+        let arg = args[0];
+        match op.opcode {
+            0 => r[0] >>= Self::combo(arg, r),
+            1 => r[1] ^= arg as u64,
+            2 => r[1] = Self::combo(arg, r) % 8,
+            3 => {
+                if r[0] != 0 {
+                    return Flow::Jump(arg as usize);
+                }
+            }
+            4 => r[1] ^= r[2],
+            5 => m.output.push((Self::combo(arg, r) % 8) as u8),
+            6 => r[1] = r[0] >> Self::combo(arg, r),
+            7 => r[2] = r[0] >> Self::combo(arg, r),
+            _ => unreachable!(),
+        }
+
+        Flow::Next
+    }
+
+    fn operand_name(kind: OperandKind, value: u8) -> String {
+        match (kind, value) {
+            (OperandKind::Combo, 4..=6) => ((b'a' + value - 4) as char).to_string(),
+            (OperandKind::Combo, 7) => "X".to_owned(),
+            _ => value.to_string(),
+        }
+    }
+
+    fn parse_operand(kind: OperandKind, s: &str) -> Option<u8> {
+        match (kind, s) {
+            (OperandKind::Combo, "a") => Some(4),
+            (OperandKind::Combo, "b") => Some(5),
+            (OperandKind::Combo, "c") => Some(6),
+            (OperandKind::Combo, "X") => Some(7),
+            _ => s.parse().ok().filter(|v| *v < 8),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{reverse_search, Stop};
+
+    #[test]
+    fn toy_programs() {
+        let mut m = Machine::<ThreeBit>::new(&[2, 6], [0, 0, 9]);
+        assert_eq!(m.run(), Stop::Halted);
+        assert_eq!(m.registers[1], 1);
+
+        let program = [5, 0, 5, 1, 5, 4];
+        let mut m = Machine::<ThreeBit>::new(&program, [10, 0, 0]);
+        m.run();
+        assert_eq!(m.output, vec![0, 1, 2]);
+
+        let program = [0, 1, 5, 4, 3, 0];
+        let mut m = Machine::<ThreeBit>::new(&program, [2024, 0, 0]);
+        m.run();
+        assert_eq!(m.output, vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        assert_eq!(m.registers[0], 0);
+        assert_eq!(m.steps, 33);
+    }
+
+    #[test]
+    fn breakpoints_and_traces() {
+        let program = [0, 1, 5, 4, 3, 0];
+        let mut m = Machine::<ThreeBit>::new(&program, [729, 0, 0]);
+        m.add_breakpoint(4);
+        assert_eq!(m.run(), Stop::Breakpoint(4));
+        assert_eq!(m.output, vec![4]);
+        assert_eq!(m.run(), Stop::Breakpoint(4));
+        assert_eq!(m.output, vec![4, 6]);
+
+        m.clear_breakpoints();
+        let mut jumps = 0;
+        let stop = m.run_traced(|_, op, _| {
+            if op.mnemonic == "JNZ" {
+                jumps += 1;
+            }
+            jumps < 3
+        });
+        assert_eq!(stop, Stop::Hook(4));
+        assert_eq!(m.output, vec![4, 6, 3, 5]);
+    }
+
+    #[test]
+    fn finds_quine_input() {
+        let program = [0, 3, 5, 4, 3, 0];
+        let mut m = Machine::<ThreeBit>::new(&program, [0, 0, 0]);
+        assert_eq!(
+            reverse_search(&mut m, &program, 8, |a| [a, 0, 0]),
+            Some(117440)
+        );
+    }
+}